@@ -50,4 +50,7 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Replay a Dot4Gravity game offline, printing the board after each move.
+	Replay(crate::replay::ReplayCmd),
 }
@@ -0,0 +1,156 @@
+//! Offline replay of Dot4Gravity games, to check the moves of a game without a running chain.
+
+use std::path::Path;
+
+use ajuna_node_template_runtime::{AccountId, BoardGameModes, BoardMove};
+use codec::Decode;
+use pallet_ajuna_board::{
+	dot4gravity::{BoardId, Game, GameState, Seed},
+	types::{GameMode, TurnBasedGame},
+};
+
+/// The `replay` command used to replay a Dot4Gravity game move by move.
+#[derive(Debug, clap::Parser)]
+pub struct ReplayCmd {
+	/// Board the game was played on, which its bombs are bound to.
+	#[arg(long, default_value_t = 0)]
+	pub board_id: BoardId,
+
+	/// Game mode whose rules the game was played with.
+	#[arg(long, default_value_t = 0)]
+	pub mode: GameMode,
+
+	/// Seed the board of the game was created with.
+	#[arg(long)]
+	pub seed: Seed,
+
+	/// Player playing the first turn.
+	#[arg(long)]
+	pub player_1: AccountId,
+
+	/// Player playing the second turn.
+	#[arg(long)]
+	pub player_2: AccountId,
+
+	/// Player playing the third turn, in games of three or more players.
+	#[arg(long)]
+	pub player_3: Option<AccountId>,
+
+	/// Player playing the fourth turn, in games of four players.
+	#[arg(long, requires = "player_3")]
+	pub player_4: Option<AccountId>,
+
+	/// Moves of the game in the order they were played, each with the player who played it, as
+	/// logged by the board pallet. Either as a JSON list or as SCALE encoded hex, read from a file
+	/// when given its path.
+	#[arg(long)]
+	pub moves: String,
+}
+
+impl ReplayCmd {
+	/// Run the replay, stopping at the first illegal move.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let moves = self.moves()?;
+		let config = BoardGameModes::get()
+			.get(self.mode as usize)
+			.copied()
+			.ok_or_else(|| sc_cli::Error::Input(format!("Unknown game mode {}", self.mode)))?;
+		let players = [&self.player_1, &self.player_2]
+			.into_iter()
+			.chain(self.player_3.as_ref())
+			.chain(self.player_4.as_ref())
+			.cloned()
+			.collect();
+		let state = Game::new_game_with_config(self.board_id, players, Some(self.seed), config)
+			.map_err(|error| {
+				sc_cli::Error::Input(format!("Invalid players for the game: {error:?}"))
+			})?;
+		println!("Board with seed {}:\n{}", self.seed, state.board);
+
+		let state = replay(state, moves)?;
+
+		match state.winners().as_slice() {
+			[] => println!("The game has not been won"),
+			winners => println!(
+				"{} won the game",
+				winners.iter().map(ToString::to_string).collect::<Vec<_>>().join(" and ")
+			),
+		}
+		Ok(())
+	}
+
+	fn moves(&self) -> sc_cli::Result<Vec<BoardMove>> {
+		let moves = if Path::new(&self.moves).is_file() {
+			std::fs::read_to_string(&self.moves)?
+		} else {
+			self.moves.clone()
+		};
+		let moves = moves.trim();
+
+		if moves.starts_with("0x") {
+			let encoded = sp_core::bytes::from_hex(moves)
+				.map_err(|error| sc_cli::Error::Input(format!("Invalid hex moves: {error}")))?;
+			Vec::<BoardMove>::decode(&mut encoded.as_slice())
+				.map_err(|error| sc_cli::Error::Input(format!("Invalid SCALE moves: {error}")))
+		} else {
+			serde_json::from_str(moves)
+				.map_err(|error| sc_cli::Error::Input(format!("Invalid JSON moves: {error}")))
+		}
+	}
+}
+
+/// Plays the moves in order, each by the player who played it as hits can be claimed out of turn,
+/// printing the board after each of them and stopping at the first illegal one.
+fn replay(
+	mut state: GameState<AccountId>,
+	moves: Vec<BoardMove>,
+) -> sc_cli::Result<GameState<AccountId>> {
+	for (index, BoardMove { player, turn, .. }) in moves.into_iter().enumerate() {
+		state = <Game<AccountId> as TurnBasedGame>::play_turn(player.clone(), state, turn.clone())
+			.map_err(|error| {
+				sc_cli::Error::Input(format!(
+					"Move {} by {player} is illegal: {turn:?} failed with {error:?}",
+					index + 1
+				))
+			})?;
+		println!("Move {} by {player}: {turn:?}\n{}", index + 1, state.board);
+	}
+	Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use pallet_ajuna_board::{
+		dot4gravity::{Board, Coordinates, GameConfig, HashSalt, Side},
+		types::Turn,
+	};
+
+	#[test]
+	fn moves_are_replayed_by_the_players_who_played_them() {
+		let [alice, bob, charlie] = [1, 2, 3].map(|byte| AccountId::from([byte; 32]));
+		let config = GameConfig { players: 3, ..GameConfig::HIDDEN_BOMBS };
+		let players = vec![alice.clone(), bob.clone(), charlie.clone()];
+		let mut state = Game::new_game_with_config(0, players, Some(7), config).unwrap();
+		state.board = Board::with_size(config.board_width, config.board_height);
+
+		let salt = HashSalt::repeat_byte(3);
+		let bomb = Coordinates::new(config.board_height - 1, 0);
+		let commitment = Game::hash_coordinates(bomb, salt, &alice, 0);
+		let moves = [
+			(alice.clone(), Turn::HideBomb(commitment)),
+			(bob, Turn::DropStone((Side::North, 0))),
+			// Charlie has the turn when Alice claims the hit
+			(alice.clone(), Turn::ClaimHit(bomb, salt)),
+			(charlie, Turn::DropStone((Side::North, 1))),
+		]
+		.into_iter()
+		.enumerate()
+		.map(|(block, (player, turn))| BoardMove { player, turn, block: block as u32 })
+		.collect();
+
+		let state = replay(state, moves).unwrap();
+		assert!(state.bombs_placed.iter().all(|bombs| bombs.is_empty()));
+		assert_eq!(state.next_player, alice);
+	}
+}
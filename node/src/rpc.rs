@@ -7,7 +7,9 @@
 
 use std::sync::Arc;
 
-use ajuna_node_template_runtime::{opaque::Block, AccountId, Balance, Index};
+use ajuna_node_template_runtime::{
+	opaque::Block, AccountId, Balance, BoardGame, BoardId, BoardMove, GameResult, Index,
+};
 use jsonrpsee::RpcModule;
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
@@ -16,6 +18,8 @@ use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 
 pub use sc_rpc_api::DenyUnsafe;
 
+pub mod ajuna_board;
+
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
 	/// The client instance to use.
@@ -37,8 +41,17 @@ where
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: pallet_ajuna_board::runtime_api::AjunaBoardApi<
+		Block,
+		AccountId,
+		BoardId,
+		BoardGame,
+		BoardMove,
+		GameResult,
+	>,
 	P: TransactionPool + 'static,
 {
+	use ajuna_board::{AjunaBoard, AjunaBoardApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
@@ -46,7 +59,8 @@ where
 	let FullDeps { client, pool, deny_unsafe } = deps;
 
 	module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(AjunaBoard::new(client).into_rpc())?;
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
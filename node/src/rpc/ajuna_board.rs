@@ -0,0 +1,146 @@
+//! RPC methods to query the board games of the Ajuna board pallet.
+
+use std::sync::Arc;
+
+use ajuna_node_template_runtime::{
+	opaque::Block, AccountId, BoardGame, BoardId, BoardMove, GameResult,
+};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_ajuna_board::runtime_api::AjunaBoardApi as AjunaBoardRuntimeApi;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+
+/// Error code returned when a call into the runtime fails.
+const RUNTIME_ERROR: i32 = 1;
+
+/// Board game RPC methods.
+#[rpc(client, server)]
+pub trait AjunaBoardApi<BlockHash> {
+	/// Board the account is currently playing in, if any.
+	#[method(name = "ajunaBoard_gameOf")]
+	fn game_of(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<Option<BoardId>>;
+
+	/// The board game with the given id.
+	#[method(name = "ajunaBoard_board")]
+	fn board(&self, board_id: BoardId, at: Option<BlockHash>) -> RpcResult<Option<BoardGame>>;
+
+	/// Turns played on the board game with the given id, in order.
+	#[method(name = "ajunaBoard_moves")]
+	fn moves(&self, board_id: BoardId, at: Option<BlockHash>) -> RpcResult<Vec<BoardMove>>;
+
+	/// Text rendering of the state of the board game with the given id.
+	#[method(name = "ajunaBoard_renderBoard")]
+	fn render_board(&self, board_id: BoardId, at: Option<BlockHash>) -> RpcResult<Option<String>>;
+
+	/// Result of the finished board game with the given id.
+	#[method(name = "ajunaBoard_gameResult")]
+	fn game_result(
+		&self,
+		board_id: BoardId,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<GameResult>>;
+
+	/// Player that should play the next turn of the board game.
+	#[method(name = "ajunaBoard_nextPlayer")]
+	fn next_player(&self, board_id: BoardId, at: Option<BlockHash>)
+		-> RpcResult<Option<AccountId>>;
+
+	/// Whether the account is waiting in the matchmaking queue.
+	#[method(name = "ajunaBoard_isQueued")]
+	fn is_queued(&self, account: AccountId, at: Option<BlockHash>) -> RpcResult<bool>;
+}
+
+/// Provides the board game RPC methods, querying the runtime of the client.
+pub struct AjunaBoard<C> {
+	client: Arc<C>,
+}
+
+impl<C> AjunaBoard<C> {
+	/// Create new `AjunaBoard` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+fn runtime_error(error: impl ToString) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(
+		RUNTIME_ERROR,
+		"Unable to query the board games.",
+		Some(error.to_string()),
+	))
+	.into()
+}
+
+impl<C> AjunaBoardApiServer<<Block as BlockT>::Hash> for AjunaBoard<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AjunaBoardRuntimeApi<Block, AccountId, BoardId, BoardGame, BoardMove, GameResult>,
+{
+	fn game_of(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<BoardId>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().game_of(at_hash, account).map_err(runtime_error)
+	}
+
+	fn board(
+		&self,
+		board_id: BoardId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<BoardGame>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().board(at_hash, board_id).map_err(runtime_error)
+	}
+
+	fn moves(
+		&self,
+		board_id: BoardId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<BoardMove>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().moves(at_hash, board_id).map_err(runtime_error)
+	}
+
+	fn render_board(
+		&self,
+		board_id: BoardId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<String>> {
+		let board_game = self.board(board_id, at)?;
+		Ok(board_game.map(|board_game| board_game.state.to_string()))
+	}
+
+	fn game_result(
+		&self,
+		board_id: BoardId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<GameResult>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().game_result(at_hash, board_id).map_err(runtime_error)
+	}
+
+	fn next_player(
+		&self,
+		board_id: BoardId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<AccountId>> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().next_player(at_hash, board_id).map_err(runtime_error)
+	}
+
+	fn is_queued(
+		&self,
+		account: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<bool> {
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client.runtime_api().is_queued(at_hash, account).map_err(runtime_error)
+	}
+}
@@ -23,10 +23,15 @@ use crate::{
 use frame_benchmarking::{account, benchmarks};
 use frame_support::assert_ok;
 use frame_system::RawOrigin;
+use pallet_ajuna_matchmaker::Pallet as AjunaMatchmaker;
 use sp_runtime::SaturatedConversion;
+use sp_std::ops::Range;
 
 const SEED: u32 = 0;
 
+/// Number of players waiting in the bracket a player is queued in or leaves
+const QUEUED_PLAYERS: u32 = 1_000;
+
 fn players<Player: Decode + Ord>(how_many: u32) -> Vec<Player> {
 	(0..how_many).map(|i| account("player", i, SEED)).collect()
 }
@@ -35,12 +40,113 @@ fn assert_last_event<T: Config>(event: <T as Config>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(event.into());
 }
 
-fn create_new_game<T: Config>(players: Vec<T::AccountId>) {
-	assert_ok!(AjunaBoard::<T>::queue(RawOrigin::Signed(players[0].clone()).into()));
-	assert_ok!(AjunaBoard::<T>::queue(RawOrigin::Signed(players[1].clone()).into()));
+/// Queues the players with the given indices in bracket, without matching them
+fn fill_bracket<T: Config>(bracket: Bracket, indices: Range<u32>) {
+	indices.for_each(|i| {
+		assert!(T::Matchmaker::enqueue(account("queued", i, SEED), bracket));
+	});
+}
+
+/// Funds the player with the lowest stake tier, if there is any, returning it
+fn stake_of<T: Config>(player: &T::AccountId) -> Option<BalanceOf<T>> {
+	let stake = T::StakeTiers::get().first().copied()?;
+	T::Currency::make_free_balance_be(player, stake.saturating_add(T::Currency::minimum_balance()));
+	Some(stake)
+}
+
+/// Game mode played by the most players, with their number
+fn largest_game_mode<T: Config>() -> (GameMode, u32) {
+	T::GameModes::get()
+		.iter()
+		.map(T::Game::players)
+		.enumerate()
+		.max_by_key(|(_, players)| *players)
+		.map(|(mode, players)| (mode as GameMode, players))
+		.unwrap()
+}
+
+/// Creates the party with the given index, returning its leader
+fn create_party<T: Config + pallet_ajuna_matchmaker::Config>(
+	party: u32,
+	size: u32,
+) -> T::AccountId {
+	let leader: T::AccountId = account("leader", party, SEED);
+	assert_ok!(AjunaMatchmaker::<T>::create_party(RawOrigin::Signed(leader.clone()).into()));
+	(1..size).for_each(|i| {
+		let member: T::AccountId = account("member", i, party);
+		let origin = RawOrigin::Signed(leader.clone()).into();
+		assert_ok!(AjunaMatchmaker::<T>::invite(origin, member.clone()));
+		let origin = RawOrigin::Signed(member).into();
+		assert_ok!(AjunaMatchmaker::<T>::accept_invitation(origin, leader.clone()));
+	});
+	leader
+}
+
+/// Queues in bracket as many parties of each of the given sizes as fit in a match of the given
+/// number of players, filling the lanes of the tickets searched for it
+fn enqueue_parties_of_sizes<T: Config + pallet_ajuna_matchmaker::Config>(
+	bracket: Bracket,
+	players: u32,
+	sizes: impl Iterator<Item = u32>,
+) {
+	let mut party = 0;
+	for size in sizes {
+		(0..players / size).for_each(|_| {
+			party += 1;
+			let leader = create_party::<T>(party, size);
+			assert!(T::Matchmaker::enqueue_party(leader, bracket));
+		});
+	}
+}
+
+fn set_phase<T: Config>(board_id: T::BoardId, phase: GamePhase) {
+	BoardGames::<T>::mutate(board_id, |board_game| {
+		if let Some(board_game) = board_game {
+			board_game.phase = phase;
+		}
+	});
 }
 
-fn create_and_play_until_win<T: Config>(players: Vec<T::AccountId>) {
+/// Queues the players in the game mode with the most players, staking the lowest stake tier if
+/// asked to, until they are matched into a new game
+fn create_game<T: Config>(players: &[T::AccountId], staked: bool) {
+	let (mode, _) = largest_game_mode::<T>();
+	for player in players {
+		let stake = if staked { stake_of::<T>(player) } else { None };
+		assert_ok!(AjunaBoard::<T>::queue(RawOrigin::Signed(player.clone()).into(), stake, mode));
+	}
+}
+
+fn create_staked_game<T: Config>(players: &[T::AccountId]) {
+	create_game::<T>(players, true);
+}
+
+fn create_new_game<T: Config>(players: &[T::AccountId]) {
+	create_game::<T>(players, false);
+}
+
+/// Players of the game outside the team of the given player
+fn opponents_of<T: Config>(board_id: T::BoardId, player: &T::AccountId) -> Vec<T::AccountId> {
+	let board_game = BoardGames::<T>::get(board_id).unwrap();
+	let team = AjunaBoard::<T>::teams_of(&board_game, &[player.clone()]);
+	board_game
+		.players
+		.iter()
+		.filter(|other| !team.contains(other))
+		.cloned()
+		.collect()
+}
+
+/// Sides and columns each player drops their stones from, four times from the first and then
+/// three times from the second. Only the stones of the first player ever form squares.
+const LANES: [[(Side, u8); 2]; 4] = [
+	[(Side::North, 0), (Side::South, 1)],
+	[(Side::North, 9), (Side::North, 7)],
+	[(Side::North, 5), (Side::North, 5)],
+	[(Side::South, 2), (Side::South, 2)],
+];
+
+fn create_and_play_until_win<T: Config>(players: &[T::AccountId]) {
 	let salt = HashSalt::from_slice(&[12; 32]);
 	// The seed below generates the following board, where o is empty and x is block:
 	// [o, o, o, o, o, o, o, o, o, o],
@@ -54,49 +160,88 @@ fn create_and_play_until_win<T: Config>(players: Vec<T::AccountId>) {
 	// [x, o, o, o, o, o, o, o, o, o],
 	// [o, o, o, o, o, o, o, o, o, o],
 	GameSeed::<T>::put(7357);
-	create_new_game::<T>(players.clone());
-
-	let mut players = players.into_iter();
-	let player_1: T::RuntimeOrigin = RawOrigin::Signed(players.next().unwrap()).into();
-	let player_2: T::RuntimeOrigin = RawOrigin::Signed(players.next().unwrap()).into();
-
-	let each_player_drops_bomb = |coord: Coordinates| {
-		let drop_bomb: T::PlayersTurn = Turn::PlaceBomb(coord, salt).into();
-		let _ = AjunaBoard::<T>::play(player_1.clone(), drop_bomb.clone());
-		let _ = AjunaBoard::<T>::play(player_2.clone(), drop_bomb);
-	};
-	let each_player_drops_stone = |win_position: (Side, u8), lose_position: (Side, u8)| {
-		let win: T::PlayersTurn = Turn::DropStone(win_position).into();
-		let lose: T::PlayersTurn = Turn::DropStone(lose_position).into();
-		let _ = AjunaBoard::<T>::play(player_1.clone(), win);
-		let _ = AjunaBoard::<T>::play(player_2.clone(), lose);
-	};
+	create_new_game::<T>(players);
+
+	let origins = players
+		.iter()
+		.map(|player| RawOrigin::Signed(player.clone()).into())
+		.collect::<Vec<T::RuntimeOrigin>>();
 
 	// Bomb phase
-	each_player_drops_bomb(Coordinates::new(9, 9));
-	each_player_drops_bomb(Coordinates::new(8, 8));
-	each_player_drops_bomb(Coordinates::new(7, 7));
-
-	// Stone phase
-	let win_position_1 = (Side::North, 0);
-	let lose_position_1 = (Side::North, 9);
-	each_player_drops_stone(win_position_1, lose_position_1);
-	each_player_drops_stone(win_position_1, lose_position_1);
-	each_player_drops_stone(win_position_1, lose_position_1);
-	each_player_drops_stone(win_position_1, lose_position_1);
-
-	let win_position_2 = (Side::South, 1);
-	let lose_position_2 = (Side::North, 7);
-	each_player_drops_stone(win_position_2, lose_position_2);
-	each_player_drops_stone(win_position_2, lose_position_2);
-	each_player_drops_stone(win_position_2, lose_position_2);
+	for coord in [Coordinates::new(9, 9), Coordinates::new(8, 8), Coordinates::new(7, 7)] {
+		for origin in &origins {
+			let turn: T::PlayersTurn = Turn::PlaceBomb(coord, salt).into();
+			assert_ok!(AjunaBoard::<T>::play(origin.clone(), turn));
+		}
+	}
+
+	// Stone phase, leaving the first player a stone short of its third square
+	for round in 0..7 {
+		for (origin, lanes) in origins.iter().zip(LANES) {
+			let turn: T::PlayersTurn = Turn::DropStone(lanes[round / 4]).into();
+			assert_ok!(AjunaBoard::<T>::play(origin.clone(), turn));
+		}
+	}
 }
 
 benchmarks! {
+	where_clause { where T: pallet_ajuna_matchmaker::Config }
+
+	// The player joins a full bracket of the game mode with the most players, whose first player
+	// is matched into a new game after the oldest tickets of every size are searched
+	queue {
+		let (mode, players) = largest_game_mode::<T>();
+		let player: T::AccountId = account("player", 0, SEED);
+		let stake = stake_of::<T>(&player);
+		let bracket = AjunaBoard::<T>::bracket_for(&player, stake, mode).unwrap();
+		let size = <T as pallet_ajuna_matchmaker::Config>::MaxPartySize::get().min(players);
+		fill_bracket::<T>(bracket, 0..1);
+		enqueue_parties_of_sizes::<T>(bracket, players, 2..=size);
+		fill_bracket::<T>(bracket, 1..QUEUED_PLAYERS);
+	}: _(RawOrigin::Signed(player.clone()), stake, mode)
+	verify {
+		let first: T::AccountId = account("queued", 0, SEED);
+		assert!(PlayerBoards::<T>::contains_key(&first));
+		assert!(T::Matchmaker::is_queued(&player));
+	}
+
+	// The party completes a match of the game mode with the most players, after the oldest
+	// tickets of every other size are searched
+	queue_party {
+		let (mode, players) = largest_game_mode::<T>();
+		let size = <T as pallet_ajuna_matchmaker::Config>::MaxPartySize::get().min(players);
+		let leader = create_party::<T>(0, size - 1);
+		let bracket = AjunaBoard::<T>::bracket_for(&leader, None, mode).unwrap();
+		fill_bracket::<T>(bracket, 0..players - (size - 1));
+		let other_sizes = (2..=size).filter(|other| *other != size - 1);
+		enqueue_parties_of_sizes::<T>(bracket, players, other_sizes);
+	}: _(RawOrigin::Signed(leader.clone()), mode)
+	verify {
+		assert!(PlayerBoards::<T>::contains_key(&leader));
+	}
+
+	// The player leaves from the middle of a full bracket
+	unqueue {
+		let player: T::AccountId = account("player", 0, SEED);
+		let stake = stake_of::<T>(&player);
+		let bracket = AjunaBoard::<T>::bracket_for(&player, stake, 0).unwrap();
+		fill_bracket::<T>(bracket, 0..QUEUED_PLAYERS / 2);
+		assert!(T::Matchmaker::enqueue(player.clone(), bracket));
+		QueuedBrackets::<T>::insert(&player, bracket);
+		if let Some(stake) = stake {
+			T::Currency::reserve(&player, stake)?;
+			PlayerStakes::<T>::insert(&player, stake);
+		}
+		fill_bracket::<T>(bracket, QUEUED_PLAYERS / 2..QUEUED_PLAYERS);
+	}: _(RawOrigin::Signed(player.clone()))
+	verify {
+		assert!(!T::Matchmaker::is_queued(&player));
+	}
+
 	play {
 		let salt = HashSalt::from_slice(&[12; 32]);
 		let players = players::<T::AccountId>(T::Players::get());
-		create_new_game::<T>(players.clone());
+		create_new_game::<T>(&players);
 
 		let player_1 = players.into_iter().next().unwrap();
 		let turn = Turn::PlaceBomb(Coordinates::new(1, 2), salt);
@@ -105,13 +250,97 @@ benchmarks! {
 	play_turn_until_finished {
 		let board_id = T::BoardId::saturated_from(0_u32);
 		let players = players::<T::AccountId>(T::Players::get());
-		create_and_play_until_win::<T>( players.clone());
+		create_and_play_until_win::<T>(&players);
+
+		let winner = players.into_iter().next().unwrap();
+		let board_game = BoardGames::<T>::get(board_id).unwrap();
+		let winners = AjunaBoard::<T>::teams_of(&board_game, &[winner.clone()]);
+		let turn = Turn::DropStone((Side::South, 1));
+	}: play(RawOrigin::Signed(winner), turn.into())
+	verify {
+		assert_last_event::<T>(Event::GameFinished { board_id, winners }.into());
+	}
+
+	clear_board {
+		let board_id = T::BoardId::saturated_from(0_u32);
+		let players = players::<T::AccountId>(T::Players::get());
+		create_and_play_until_win::<T>(&players);
 
 		let winner = players.into_iter().next().unwrap();
 		let turn = Turn::DropStone((Side::South, 1));
-	}: play(RawOrigin::Signed(winner.clone()), turn.into())
+		assert_ok!(AjunaBoard::<T>::play(RawOrigin::Signed(winner).into(), turn.into()));
+	}: _(RawOrigin::Root, board_id)
+	verify {
+		assert!(!BoardGames::<T>::contains_key(board_id));
+	}
+
+	// An opponent claims the staked game of the player whose turn has timed out
+	claim_timeout {
+		let board_id = T::BoardId::saturated_from(0_u32);
+		let players = players::<T::AccountId>(T::Players::get());
+		create_staked_game::<T>(&players);
+
+		let idle_player = AjunaBoard::<T>::next_player(board_id).unwrap();
+		let winners = opponents_of::<T>(board_id, &idle_player);
+		let claimer = winners[0].clone();
+		let now = frame_system::Pallet::<T>::block_number();
+		let timed_out = now.saturating_add(T::IdleBoardTimeout::get()).saturating_add(1_u32.into());
+		frame_system::Pallet::<T>::set_block_number(timed_out);
+	}: _(RawOrigin::Signed(claimer))
+	verify {
+		assert_last_event::<T>(Event::GameFinished { board_id, winners }.into());
+	}
+
+	forfeit {
+		let board_id = T::BoardId::saturated_from(0_u32);
+		let players = players::<T::AccountId>(T::Players::get());
+		create_staked_game::<T>(&players);
+
+		let player_1 = players.into_iter().next().unwrap();
+		let winners = opponents_of::<T>(board_id, &player_1);
+	}: _(RawOrigin::Signed(player_1))
+	verify {
+		assert_last_event::<T>(Event::GameFinished { board_id, winners }.into());
+	}
+
+	// The last commitment moves the board on to the reveals
+	commit {
+		let board_id = T::BoardId::saturated_from(0_u32);
+		let players = players::<T::AccountId>(T::Players::get());
+		create_new_game::<T>(&players);
+		set_phase::<T>(board_id, GamePhase::AwaitingCommitments);
+
+		let (last, others) = players.split_last().unwrap();
+		for (i, player) in others.iter().enumerate() {
+			Commitments::<T>::insert(board_id, player, H256::repeat_byte(i as u8 + 1));
+		}
+	}: _(RawOrigin::Signed(last.clone()), H256::repeat_byte(players.len() as u8))
+	verify {
+		let phase = BoardGames::<T>::get(board_id).map(|board_game| board_game.phase);
+		assert_eq!(phase, Some(GamePhase::AwaitingReveals));
+	}
+
+	// The last reveal seeds the board and starts the game
+	reveal {
+		let board_id = T::BoardId::saturated_from(0_u32);
+		let players = players::<T::AccountId>(T::Players::get());
+		create_new_game::<T>(&players);
+		set_phase::<T>(board_id, GamePhase::AwaitingReveals);
+
+		let secrets =
+			(1..=players.len()).map(|i| H256::repeat_byte(i as u8)).collect::<Vec<_>>();
+		for (player, secret) in players.iter().zip(&secrets) {
+			let commitment = AjunaBoard::<T>::commitment_of(board_id, secret, player);
+			Commitments::<T>::insert(board_id, player, commitment);
+		}
+		let (last, others) = players.split_last().unwrap();
+		for (player, secret) in others.iter().zip(&secrets) {
+			Reveals::<T>::insert(board_id, player, secret);
+		}
+		let last_secret = secrets[others.len()];
+	}: _(RawOrigin::Signed(last.clone()), last_secret)
 	verify {
-		assert_last_event::<T>(Event::GameFinished { board_id, winner }.into());
+		assert_last_event::<T>(Event::GameStarted { board_id }.into());
 	}
 
 	impl_benchmark_test_suite!(
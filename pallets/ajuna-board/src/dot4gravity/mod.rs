@@ -21,8 +21,6 @@ use scale_info::{prelude::vec::Vec, TypeInfo};
 use sp_core::H256;
 use sp_io::hashing::blake2_256;
 
-use sp_runtime::traits::{BlakeTwo256, Hash};
-
 #[cfg(test)]
 mod tests;
 mod traits;
@@ -33,26 +31,38 @@ const INITIAL_SEED: Seed = 123_456;
 const INCREMENT: Seed = 74;
 const MULTIPLIER: Seed = 75;
 const MODULUS: Seed = Seed::pow(2, 16);
-
-const BOARD_WIDTH: u8 = 10;
-const BOARD_HEIGHT: u8 = 10;
-const NUM_OF_PLAYERS: usize = 2;
-const BOMB_AMOUNT_PER_PLAYER: usize = 3;
-const BOMB_ENERGY_PER_PLAYER: u8 = 5;
-const NUM_OF_BLOCKS: u8 = 10;
-
+/// Random cells drawn for each block before the remaining blocks are shuffled into free cells.
+const BLOCK_DRAWS_PER_BLOCK: u16 = 4;
+
+/// Largest board supported by any game configuration.
+pub const MAX_BOARD_WIDTH: u8 = 10;
+pub const MAX_BOARD_HEIGHT: u8 = 10;
+/// Most bombs a player can place at once in any game configuration.
+pub const MAX_BOMBS_PER_PLAYER: u8 = 5;
+/// Fewest and most players of a game.
+pub const MIN_PLAYERS: u8 = 2;
+pub const MAX_PLAYERS: u8 = 4;
+
+pub type BoardId = u32;
 pub type PlayerIndex = u8;
+pub type TeamIndex = u8;
 pub type Position = u8;
 pub type Seed = u32;
 pub type HashSalt = H256;
 pub type HashedCoordinates = H256;
+/// One item for each player of a game, in turn order.
+pub type PerPlayer<T> = BoundedVec<T, ConstU32<{ MAX_PLAYERS as u32 }>>;
+/// Bombs placed by a player.
+pub type PlacedBombs = BoundedVec<HashedCoordinates, ConstU32<{ MAX_BOMBS_PER_PLAYER as u32 }>>;
 
 /// Represents a cell of the board.
 #[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum Cell {
 	Empty,
 	Block,
-	Stone(PlayerIndex),
+	/// Stone of a player, with the side it was dropped from.
+	Stone(PlayerIndex, Side),
 }
 
 impl Default for Cell {
@@ -64,12 +74,27 @@ impl Default for Cell {
 impl Cell {
 	/// Tells if a cell is suitable for dropping a stone.
 	fn is_stone_droppable(&self) -> bool {
-		!matches!(self, Cell::Block | Cell::Stone(_))
+		!matches!(self, Cell::Block | Cell::Stone(..))
+	}
+
+	/// Index of the player owning the stone in the cell, if any.
+	pub fn player_index(&self) -> Option<PlayerIndex> {
+		match self {
+			Cell::Stone(player_index, _) => Some(*player_index),
+			_ => None,
+		}
 	}
 }
 
+/// Next seed of the generator. Seeds of the whole range are reduced into its modulus first, as
+/// they would otherwise saturate the multiplication and all lead to the same seed.
+fn linear_congruential_generator(seed: Seed) -> Seed {
+	(MULTIPLIER * (seed % MODULUS) + INCREMENT) % MODULUS
+}
+
 /// Coordinates for a cell in the board.
 #[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct Coordinates {
 	pub row: u8,
 	pub col: u8,
@@ -80,36 +105,33 @@ impl Coordinates {
 		Self { row, col }
 	}
 
-	fn random(seed: Seed) -> (Self, Seed) {
-		let linear_congruential_generator = |seed: Seed| -> Seed {
-			MULTIPLIER.saturating_mul(seed).saturating_add(INCREMENT) % MODULUS
-		};
-
+	fn random(seed: Seed, board: &Board) -> (Self, Seed) {
 		let random_seed_1 = linear_congruential_generator(seed);
 		let random_seed_2 = linear_congruential_generator(random_seed_1);
 
 		(
 			Coordinates::new(
-				(random_seed_1 % (BOARD_WIDTH as Seed - 1)) as u8,
-				(random_seed_2 % (BOARD_HEIGHT as Seed - 1)) as u8,
+				(random_seed_1 % (board.height as Seed - 1)) as u8,
+				(random_seed_2 % (board.width as Seed - 1)) as u8,
 			),
 			random_seed_2,
 		)
 	}
 
 	/// Tells if a cell is in the opposite of a side.
-	fn is_opposite_cell(&self, side: Side) -> bool {
+	fn is_opposite_cell(&self, side: Side, board: &Board) -> bool {
 		match side {
-			Side::North => self.row == BOARD_HEIGHT - 1,
+			Side::North => self.row == board.height - 1,
 			Side::East => self.col == 0,
 			Side::South => self.row == 0,
-			Side::West => self.col == BOARD_WIDTH - 1,
+			Side::West => self.col == board.width - 1,
 		}
 	}
 }
 
 /// Sides of the board from which a player can drop a stone.
 #[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum Side {
 	North,
 	East,
@@ -118,18 +140,64 @@ pub enum Side {
 }
 
 impl Side {
-	fn bound_coordinates(&self, position: Position) -> Coordinates {
+	const ALL: [Side; 4] = [Side::North, Side::East, Side::South, Side::West];
+
+	/// Number of positions from which a stone can be dropped on this side.
+	fn positions(&self, board: &Board) -> Position {
+		match self {
+			Side::North | Side::South => board.width,
+			Side::East | Side::West => board.height,
+		}
+	}
+
+	/// Side across the board from this one.
+	fn opposite(&self) -> Side {
+		match self {
+			Side::North => Side::South,
+			Side::East => Side::West,
+			Side::South => Side::North,
+			Side::West => Side::East,
+		}
+	}
+
+	/// Next cell a stone dropped from this side moves to, if it is inside the board.
+	fn next_cell(&self, coordinates: &Coordinates, board: &Board) -> Option<Coordinates> {
+		let Coordinates { row, col } = *coordinates;
+		match self {
+			Side::North => row.checked_add(1).map(|row| Coordinates::new(row, col)),
+			Side::East => col.checked_sub(1).map(|col| Coordinates::new(row, col)),
+			Side::South => row.checked_sub(1).map(|row| Coordinates::new(row, col)),
+			Side::West => col.checked_add(1).map(|col| Coordinates::new(row, col)),
+		}
+		.filter(|next| next.is_inside_board(board))
+	}
+
+	/// Cell where the stone dropped at the position last landed, being the first one taken when
+	/// coming from this side.
+	fn landing_cell(&self, position: Position, board: &Board) -> Option<Coordinates> {
+		let mut cell = Some(self.bound_coordinates(position, board));
+		while let Some(coordinates) = cell {
+			if board.get_cell(&coordinates) != Cell::Empty {
+				break
+			}
+			cell = self.next_cell(&coordinates, board);
+		}
+		cell
+	}
+
+	fn bound_coordinates(&self, position: Position, board: &Board) -> Coordinates {
 		match self {
 			Side::North => Coordinates::new(0, position),
-			Side::South => Coordinates::new(BOARD_HEIGHT - 1, position),
+			Side::South => Coordinates::new(board.height - 1, position),
 			Side::West => Coordinates::new(position, 0),
-			Side::East => Coordinates::new(position, BOARD_WIDTH - 1),
+			Side::East => Coordinates::new(position, board.width - 1),
 		}
 	}
 }
 
 /// Bomb power radius levels.
 #[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum PowerLevel {
 	One,
 	Two,
@@ -146,26 +214,30 @@ impl PowerLevel {
 	}
 
 	pub fn explode<Player>(&self, game_state: &mut GameState<Player>, epicenter: &Coordinates) {
-		// Level 1 explosion always triggers
-		Self::detonate_cell(game_state, epicenter);
+		for (row_offset, col_offset) in self.blast_area() {
+			let cell = epicenter
+				.row
+				.checked_add_signed(*row_offset)
+				.zip(epicenter.col.checked_add_signed(*col_offset))
+				.map(|(row, col)| Coordinates::new(row, col));
+
+			// Cells of the blast area falling outside of the board are left out
+			if let Some(cell) = cell.filter(|cell| cell.is_inside_board(&game_state.board)) {
+				Self::detonate_cell(game_state, &cell);
+			}
+		}
+	}
 
-		if *self == PowerLevel::Two || *self == PowerLevel::Three {
+	/// Offsets from the epicenter of the cells hit by the explosion.
+	fn blast_area(&self) -> &'static [(i8, i8)] {
+		match self {
+			// Level 1 explosion always triggers
+			PowerLevel::One => &[(0, 0)],
 			// Level 2 explosion
-			let center_row = epicenter.row;
-			let center_col = epicenter.col;
-
-			Self::detonate_cell(game_state, &Coordinates::new(center_row + 1, center_col));
-			Self::detonate_cell(game_state, &Coordinates::new(center_row, center_col + 1));
-			Self::detonate_cell(game_state, &Coordinates::new(center_row - 1, center_col));
-			Self::detonate_cell(game_state, &Coordinates::new(center_row, center_col - 1));
-
-			if *self == PowerLevel::Three {
-				// Level 3 explosion
-				Self::detonate_cell(game_state, &Coordinates::new(center_row + 1, center_col + 1));
-				Self::detonate_cell(game_state, &Coordinates::new(center_row + 1, center_col - 1));
-				Self::detonate_cell(game_state, &Coordinates::new(center_row - 1, center_col + 1));
-				Self::detonate_cell(game_state, &Coordinates::new(center_row - 1, center_col - 1));
-			}
+			PowerLevel::Two => &[(0, 0), (1, 0), (0, 1), (-1, 0), (0, -1)],
+			// Level 3 explosion
+			PowerLevel::Three =>
+				&[(0, 0), (1, 0), (0, 1), (-1, 0), (0, -1), (1, 1), (1, -1), (-1, 1), (-1, -1)],
 		}
 	}
 
@@ -183,7 +255,7 @@ impl PowerLevel {
 	}
 
 	fn detonate_cell<Player>(game_state: &mut GameState<Player>, position: &Coordinates) {
-		if matches!(game_state.board.get_cell(position), Cell::Stone(_)) {
+		if matches!(game_state.board.get_cell(position), Cell::Stone(..)) {
 			game_state.board.update_cell(position, Cell::Empty);
 		}
 	}
@@ -191,9 +263,111 @@ impl PowerLevel {
 
 pub type BombEnergy = u8;
 
-#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Copy, Clone, Eq, Debug, Default, PartialEq)]
+/// Rules of a game, chosen when it is created.
+#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameConfig {
+	/// Number of players of the game.
+	pub players: u8,
+	/// Number of columns of the board.
+	pub board_width: u8,
+	/// Number of rows of the board.
+	pub board_height: u8,
+	/// Number of blocks randomly placed on the board.
+	pub blocks: u8,
+	/// Number of bombs each player can have placed at once.
+	pub bombs_per_player: u8,
+	/// Bomb energy each player starts with.
+	pub bomb_energy_per_player: BombEnergy,
+	/// Number of 2x2 squares of stones a player needs to win.
+	pub squares_to_win: u8,
+	/// Whether stones slide back toward the side they were dropped from after an explosion, until
+	/// they reach an obstacle or that side.
+	pub gravity_after_explosions: bool,
+	/// Whether the owner of a bomb can claim a hit on the opponent stone that just landed on it,
+	/// blowing the stone up. Only games of two players can hide bombs, as the hit is lost to the
+	/// turn played after it, which with more players is not the bomb owner's.
+	pub hidden_bombs: bool,
+	/// Whether the players form two teams, taking their turns alternately, whose stones count
+	/// together towards their squares.
+	pub teams: bool,
+}
+
+impl GameConfig {
+	/// Classic game on a 10x10 board.
+	pub const CLASSIC: Self = Self {
+		players: 2,
+		board_width: 10,
+		board_height: 10,
+		blocks: 10,
+		bombs_per_player: 3,
+		bomb_energy_per_player: 5,
+		squares_to_win: 3,
+		gravity_after_explosions: false,
+		hidden_bombs: false,
+		teams: false,
+	};
+
+	/// Quick game on an 8x8 board.
+	pub const QUICK: Self = Self {
+		players: 2,
+		board_width: 8,
+		board_height: 8,
+		blocks: 6,
+		bombs_per_player: 2,
+		bomb_energy_per_player: 4,
+		squares_to_win: 2,
+		gravity_after_explosions: false,
+		hidden_bombs: false,
+		teams: false,
+	};
+
+	/// Classic game where stones settle again after each explosion.
+	pub const GRAVITY: Self = Self { gravity_after_explosions: true, ..Self::CLASSIC };
+
+	/// Classic game where bombs are traps for the stones of the opponent.
+	pub const HIDDEN_BOMBS: Self = Self { hidden_bombs: true, ..Self::CLASSIC };
+
+	/// Classic game where four players play against each other.
+	pub const FREE_FOR_ALL: Self = Self { players: 4, ..Self::CLASSIC };
+
+	/// Classic game where two teams of two players play against each other.
+	pub const TEAMS: Self = Self { players: 4, teams: true, ..Self::CLASSIC };
+
+	/// Tells if the players and the board fit the supported amount and size, with room for the
+	/// blocks of the board, and if the bombs fit the supported amount. Teams need an even amount
+	/// of players, hidden bombs exactly two.
+	pub fn is_valid(&self) -> bool {
+		let cells = self.board_width as u16 * self.board_height as u16;
+		(MIN_PLAYERS..=MAX_PLAYERS).contains(&self.players) &&
+			(!self.teams || self.players % 2 == 0) &&
+			(!self.hidden_bombs || self.players == 2) &&
+			(2..=MAX_BOARD_WIDTH).contains(&self.board_width) &&
+			(2..=MAX_BOARD_HEIGHT).contains(&self.board_height) &&
+			(self.blocks as u16) < cells / 2 &&
+			self.bombs_per_player <= MAX_BOMBS_PER_PLAYER &&
+			self.squares_to_win > 0
+	}
+}
+
+impl Default for GameConfig {
+	fn default() -> Self {
+		Self::CLASSIC
+	}
+}
+
+#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Copy, Clone, Eq, Debug, PartialEq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct Board {
-	cells: [[Cell; BOARD_WIDTH as usize]; BOARD_HEIGHT as usize],
+	cells: [[Cell; MAX_BOARD_WIDTH as usize]; MAX_BOARD_HEIGHT as usize],
+	width: u8,
+	height: u8,
+}
+
+impl Default for Board {
+	fn default() -> Self {
+		Self::with_size(GameConfig::CLASSIC.board_width, GameConfig::CLASSIC.board_height)
+	}
 }
 
 impl Board {
@@ -201,19 +375,84 @@ impl Board {
 		Board::default()
 	}
 
+	/// Create an empty board of the given size, up to the largest supported board.
+	pub fn with_size(width: u8, height: u8) -> Board {
+		Board {
+			cells: Default::default(),
+			width: width.min(MAX_BOARD_WIDTH),
+			height: height.min(MAX_BOARD_HEIGHT),
+		}
+	}
+
+	/// Number of columns of the board.
+	pub fn width(&self) -> u8 {
+		self.width
+	}
+
+	/// Number of rows of the board.
+	pub fn height(&self) -> u8 {
+		self.height
+	}
+
 	fn is_stone_droppable(&self, position: &Coordinates) -> bool {
-		position.is_inside_board() && self.get_cell(position).is_stone_droppable()
+		position.is_inside_board(self) && self.get_cell(position).is_stone_droppable()
 	}
 
-	fn get_cell(&self, position: &Coordinates) -> Cell {
+	pub(crate) fn get_cell(&self, position: &Coordinates) -> Cell {
 		let cell = &self.cells[position.row as usize][position.col as usize];
 		*cell
 	}
 
-	fn update_cell(&mut self, position: &Coordinates, cell: Cell) {
+	pub(crate) fn update_cell(&mut self, position: &Coordinates, cell: Cell) {
 		self.cells[position.row as usize][position.col as usize] = cell;
 		assert_eq!(self.cells[position.row as usize][position.col as usize], cell);
 	}
+
+	/// Moves every stone back toward the side it was dropped from, one cell at a time, until it
+	/// reaches a block, another stone or that side.
+	fn settle_stones(&mut self) {
+		let mut moved = true;
+		while moved {
+			moved = false;
+			for row in 0..self.height {
+				for col in 0..self.width {
+					let position = Coordinates::new(row, col);
+					if let cell @ Cell::Stone(_, side) = self.get_cell(&position) {
+						if let Some(next) = side
+							.opposite()
+							.next_cell(&position, self)
+							.filter(|next| self.get_cell(next) == Cell::Empty)
+						{
+							self.update_cell(&next, cell);
+							self.update_cell(&position, Cell::Empty);
+							moved = true;
+						}
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Draws the board one row per line, with `.` for empty cells, `#` for blocks and the index of
+/// the owning player for stones.
+#[cfg(feature = "std")]
+impl std::fmt::Display for Board {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for row in self.cells.iter().take(self.height as usize) {
+			let cells = row
+				.iter()
+				.take(self.width as usize)
+				.map(|cell| match cell {
+					Cell::Empty => ".".to_string(),
+					Cell::Block => "#".to_string(),
+					Cell::Stone(player_index, _) => player_index.to_string(),
+				})
+				.collect::<Vec<_>>();
+			writeln!(f, "{}", cells.join(" "))?;
+		}
+		Ok(())
+	}
 }
 
 #[derive(Encode, Decode, TypeInfo, Debug, Eq, PartialEq)]
@@ -232,9 +471,27 @@ pub enum GameError {
 	NoPreviousPosition,
 	/// Tried playing when game has finished.
 	GameAlreadyFinished,
+	/// Tried claiming a bomb hit while no opponent stone just landed.
+	NoHitToClaim,
+	/// Tried creating a game with another number of players than its rules require.
+	InvalidPlayers,
+}
+
+/// A move that a player is allowed to make in the current state of the game.
+#[derive(Encode, Decode, TypeInfo, Copy, Clone, Debug, Eq, PartialEq)]
+pub enum LegalMove {
+	/// Drop a stone from a side of the board at the given position.
+	DropStone(Side, Position),
+	/// Place a bomb in any cell of the board.
+	PlaceBomb,
+	/// Detonate one of the placed bombs with the given power level.
+	DetonateBomb(PowerLevel),
+	/// Claim a hit of one of the placed bombs on the stone the opponent just dropped.
+	ClaimHit,
 }
 
 #[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct LastMove<Player> {
 	pub player: Player,
 	pub side: Side,
@@ -248,7 +505,10 @@ impl<Player> LastMove<Player> {
 }
 
 #[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct GameState<Player> {
+	/// Board the game is played on, which the bombs placed are bound to.
+	pub board_id: BoardId,
 	/// Represents random seed.
 	pub seed: Seed,
 	/// Represents the game board.
@@ -257,15 +517,36 @@ pub struct GameState<Player> {
 	pub winner: Option<Player>,
 	/// Next player turn.
 	pub next_player: Player,
+	/// Rules the game is played with.
+	pub config: GameConfig,
 	/// Players:
-	pub players: [Player; NUM_OF_PLAYERS],
+	pub players: PerPlayer<Player>,
 	/// Amount of bomb energy available per player.
-	pub bomb_energy: [(Player, BombEnergy); NUM_OF_PLAYERS],
+	pub bomb_energy: PerPlayer<(Player, BombEnergy)>,
 	/// Amount of bomb energy available per player.
-	pub bombs_placed: [BoundedVec<HashedCoordinates, ConstU32<{ BOMB_AMOUNT_PER_PLAYER as u32 }>>;
-		NUM_OF_PLAYERS],
+	pub bombs_placed: PerPlayer<PlacedBombs>,
 	/// Represents the last move.
 	pub last_move: Option<LastMove<Player>>,
+	/// Cell the stone of the last turn landed on, on which the owner of a bomb hidden there can
+	/// claim a hit until the next turn is played.
+	pub landed_stone: Option<Coordinates>,
+}
+
+/// Describes the players, their bombs and the outcome of the game above its board.
+#[cfg(feature = "std")]
+impl<Player: PartialEq + Clone + std::fmt::Debug> std::fmt::Display for GameState<Player> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for (index, (player, energy)) in self.bomb_energy.iter().enumerate() {
+			let bombs = self.bombs_placed[index].len();
+			writeln!(f, "{index}: {player:?} with {bombs} bombs placed and {energy} bomb energy")?;
+		}
+		match &self.winner {
+			Some(winner) => writeln!(f, "Won by {winner:?}")?,
+			None if self.is_draw() => writeln!(f, "Drawn")?,
+			None => writeln!(f, "Next player is {:?}", self.next_player)?,
+		}
+		write!(f, "{}", self.board)
+	}
 }
 
 impl<Player: PartialEq + Clone> GameState<Player> {
@@ -288,6 +569,17 @@ impl<Player: PartialEq + Clone> GameState<Player> {
 		}
 	}
 
+	/// Tells if the game ended without a winner: no player has a legal move left, so the game
+	/// cannot go on. Players who cannot move while others can are skipped instead.
+	pub fn is_draw(&self) -> bool {
+		self.winner.is_none() &&
+			self.players.iter().all(|player| self.turn_moves(player).is_empty())
+	}
+
+	pub fn is_finished(&self) -> bool {
+		self.winner.is_some() || self.is_draw()
+	}
+
 	pub fn is_player_turn(&self, player: &Player) -> bool {
 		self.next_player == *player
 	}
@@ -297,54 +589,245 @@ impl<Player: PartialEq + Clone> GameState<Player> {
 			.players
 			.iter()
 			.position(|this_player| this_player == player)
-			.expect("game to always start with 2 players") as u8;
+			.expect("player to be in the game") as u8;
 		player_index
 	}
 
+	/// Every move the player could make, whether or not the game is over. Out of their turn that
+	/// is at most claiming a hit.
+	fn moves_of(&self, player: &Player) -> Vec<LegalMove> {
+		if !self.is_player_in_game(player) {
+			return Vec::new()
+		}
+		let player_index = self.player_index(player);
+		let can_claim_hit = !self.bombs_placed[player_index as usize].is_empty() &&
+			self.check_claim_hit(player).is_ok();
+		if !self.is_player_turn(player) {
+			return if can_claim_hit { Vec::from([LegalMove::ClaimHit]) } else { Vec::new() }
+		}
+
+		self.turn_moves(player)
+	}
+
+	/// Every move the player could make if it were their turn.
+	fn turn_moves(&self, player: &Player) -> Vec<LegalMove> {
+		let board = &self.board;
+		let mut moves = Side::ALL
+			.iter()
+			.flat_map(|side| (0..side.positions(board)).map(move |position| (*side, position)))
+			.filter(|(side, position)| self.check_drop_stone(side, *position).is_ok())
+			.map(|(side, position)| LegalMove::DropStone(side, position))
+			.collect::<Vec<_>>();
+
+		if self.check_place_bomb(player).is_ok() {
+			moves.push(LegalMove::PlaceBomb);
+		}
+
+		let player_index = self.player_index(player);
+		if !self.bombs_placed[player_index as usize].is_empty() {
+			moves.extend(
+				[PowerLevel::One, PowerLevel::Two, PowerLevel::Three]
+					.into_iter()
+					.filter(|power_level| self.check_detonate_bomb(player, power_level).is_ok())
+					.map(LegalMove::DetonateBomb),
+			);
+			if self.check_claim_hit(player).is_ok() {
+				moves.push(LegalMove::ClaimHit);
+			}
+		}
+
+		moves
+	}
+
+	/// Checks the player has a bomb left to place, which can go off once placed. Without hidden
+	/// bombs that takes bomb energy to detonate it.
+	fn check_place_bomb(&self, player: &Player) -> Result<(), GameError> {
+		let player_index = self.player_index(player);
+		let bombs_placed = self.bombs_placed[player_index as usize].len();
+		if bombs_placed >= self.config.bombs_per_player as usize {
+			return Err(GameError::NoMoreBombsAvailable)
+		}
+		if !self.config.hidden_bombs && self.get_bomb_energy_for(player).unwrap_or_default() == 0 {
+			return Err(GameError::InsufficientBombEnergy)
+		}
+
+		Ok(())
+	}
+
+	fn check_detonate_bomb(
+		&self,
+		player: &Player,
+		power_level: &PowerLevel,
+	) -> Result<(), GameError> {
+		if !power_level.can_use_level(self.get_bomb_energy_for(player).unwrap_or_default()) {
+			return Err(GameError::InsufficientBombEnergy)
+		}
+
+		Ok(())
+	}
+
+	/// Checks a stone of an opponent of the player just landed, in a game with hidden bombs.
+	fn check_claim_hit(&self, player: &Player) -> Result<(), GameError> {
+		let landed_stone = match self.landed_stone {
+			Some(coordinates) if self.config.hidden_bombs => coordinates,
+			_ => return Err(GameError::NoHitToClaim),
+		};
+		let team = self.team_of(self.player_index(player));
+		match self.board.get_cell(&landed_stone).player_index() {
+			Some(owner) if self.team_of(owner) != team => Ok(()),
+			_ => Err(GameError::NoHitToClaim),
+		}
+	}
+
+	fn check_drop_stone(&self, side: &Side, position: Position) -> Result<(), GameError> {
+		if !self.board.is_stone_droppable(&side.bound_coordinates(position, &self.board)) {
+			return Err(GameError::InvalidStonePosition)
+		}
+
+		Ok(())
+	}
+
+	/// Player taking the turn after the current one: the first one in turn order who can make a
+	/// move, skipping those who cannot. When nobody can, the game is drawn anyway.
 	fn next_player(&self) -> &Player {
 		let current_player_index = self
 			.players
 			.iter()
 			.position(|player| *player == self.next_player)
 			.expect("next player to be a subset of players");
-		&self.players[(current_player_index + 1) % NUM_OF_PLAYERS]
+		let count = self.players.len();
+		(1..=count)
+			.map(|offset| &self.players[(current_player_index + offset) % count])
+			.find(|player| !self.turn_moves(player).is_empty())
+			.unwrap_or(&self.players[(current_player_index + 1) % count])
+	}
+
+	/// Team of the player at the given index. In team games the players alternate between the
+	/// two teams in turn order, otherwise each player is a team of its own.
+	pub fn team_of(&self, player_index: PlayerIndex) -> TeamIndex {
+		if self.config.teams {
+			player_index % 2
+		} else {
+			player_index
+		}
+	}
+
+	/// Team of each player, in turn order.
+	pub fn teams(&self) -> Vec<TeamIndex> {
+		(0..self.players.len() as PlayerIndex)
+			.map(|index| self.team_of(index))
+			.collect()
+	}
+
+	/// Number of 2x2 squares of stones each player has on the board, overlapping ones included.
+	/// In team games the stones of the team count together, so team mates share their squares.
+	pub fn squares(&self) -> Vec<u8> {
+		let board = &self.board;
+		let team_at = |row, col| {
+			board
+				.get_cell(&Coordinates::new(row, col))
+				.player_index()
+				.map(|p| self.team_of(p))
+		};
+		let mut team_squares = Vec::from_iter(self.players.iter().map(|_| 0_u8));
+
+		for row in 0..board.height - 1 {
+			for col in 0..board.width - 1 {
+				let team = team_at(row, col);
+				if let Some(team_index) = team {
+					if team == team_at(row, col + 1) &&
+						team == team_at(row + 1, col) &&
+						team == team_at(row + 1, col + 1)
+					{
+						if let Some(count) = team_squares.get_mut(team_index as usize) {
+							*count = count.saturating_add(1);
+						}
+					}
+				}
+			}
+		}
+
+		self.teams().into_iter().map(|team| team_squares[team as usize]).collect()
+	}
+
+	/// Players who won the game: the winner together with its team mates.
+	pub fn winners(&self) -> Vec<Player> {
+		let teams = self.teams();
+		let winning_team = self
+			.winner
+			.as_ref()
+			.and_then(|winner| self.players.iter().position(|player| player == winner))
+			.map(|index| teams[index]);
+		self.players
+			.iter()
+			.zip(teams.iter())
+			.filter(|(_, team)| Some(**team) == winning_team)
+			.map(|(player, _)| player.clone())
+			.collect()
+	}
+
+	/// Players ranked from first to last: the winners, if any, followed by the others ordered by
+	/// the squares they have on the board, in turn order when tied.
+	pub fn ranking(&self) -> Vec<Player> {
+		self.standings().concat()
+	}
+
+	/// Players grouped by their place in the ranking, from first to last. Players tied on their
+	/// squares share a place, so team mates always do.
+	pub fn standings(&self) -> Vec<Vec<Player>> {
+		let winners = self.winners();
+		let mut standings = Vec::<(_, Vec<Player>)>::new();
+		for (player, squares) in self.players.iter().zip(self.squares()) {
+			let place = (!winners.contains(player), core::cmp::Reverse(squares));
+			match standings.iter_mut().find(|(other_place, _)| *other_place == place) {
+				Some((_, players)) => players.push(player.clone()),
+				None => standings.push((place, Vec::from([player.clone()]))),
+			}
+		}
+		standings.sort_by_key(|(place, _)| *place);
+		standings.into_iter().map(|(_, players)| players).collect()
 	}
 }
 
 #[derive(Encode, Decode, TypeInfo)]
 pub struct Game<Player>(PhantomData<Player>);
 
-impl<Player: PartialEq + Clone> Game<Player> {
-	fn can_place_bomb(game_state: &GameState<Player>, player: &Player) -> Result<(), GameError> {
-		if game_state.winner.is_some() {
+impl<Player: PartialEq + Clone + Encode> Game<Player> {
+	/// Checks the game is still going on and it is the turn of the player.
+	fn can_play(game_state: &GameState<Player>, player: &Player) -> Result<(), GameError> {
+		if game_state.is_finished() {
 			return Err(GameError::GameAlreadyFinished)
 		}
 		if !game_state.is_player_turn(player) {
 			return Err(GameError::NotPlayerTurn)
 		}
-		let player_index = game_state.player_index(player);
-		if game_state.bombs_placed[player_index as usize].len() >= BOMB_AMOUNT_PER_PLAYER {
-			return Err(GameError::NoMoreBombsAvailable)
-		}
 
 		Ok(())
 	}
+
+	fn can_place_bomb(game_state: &GameState<Player>, player: &Player) -> Result<(), GameError> {
+		Self::can_play(game_state, player)?;
+		game_state.check_place_bomb(player)
+	}
+
 	fn can_detonate_bomb(
 		game_state: &GameState<Player>,
 		player: &Player,
 		power_level: &PowerLevel,
 	) -> Result<(), GameError> {
-		if game_state.winner.is_some() {
+		Self::can_play(game_state, player)?;
+		game_state.check_detonate_bomb(player, power_level)
+	}
+
+	/// Unlike other moves a hit can be claimed out of turn, by any player owning the bomb.
+	fn can_claim_hit(game_state: &GameState<Player>, player: &Player) -> Result<(), GameError> {
+		if game_state.is_finished() {
 			return Err(GameError::GameAlreadyFinished)
 		}
-		if !game_state.is_player_turn(player) {
-			return Err(GameError::NotPlayerTurn)
-		}
-		if !power_level.can_use_level(game_state.get_bomb_energy_for(player).unwrap_or_default()) {
-			return Err(GameError::InsufficientBombEnergy)
+		if !game_state.is_player_in_game(player) {
+			return Err(GameError::InvalidBombCoordinates)
 		}
-
-		Ok(())
+		game_state.check_claim_hit(player)
 	}
 
 	fn can_drop_stone(
@@ -353,61 +836,122 @@ impl<Player: PartialEq + Clone> Game<Player> {
 		position: Position,
 		player: &Player,
 	) -> Result<(), GameError> {
-		if game_state.winner.is_some() {
-			return Err(GameError::GameAlreadyFinished)
-		}
-		if !game_state.is_player_turn(player) {
-			return Err(GameError::NotPlayerTurn)
-		}
-		if !game_state.board.is_stone_droppable(&side.bound_coordinates(position)) {
-			return Err(GameError::InvalidStonePosition)
-		}
-		Ok(())
+		Self::can_play(game_state, player)?;
+		game_state.check_drop_stone(side, position)
 	}
 }
 
-impl<Player: PartialEq + Clone> Game<Player> {
-	/// Create a new game.
+impl<Player: PartialEq + Clone + Encode> Game<Player> {
+	/// Create a new classic game between two players, on the default board.
 	pub fn new_game(player1: Player, player2: Player, seed: Option<Seed>) -> GameState<Player> {
-		let mut board = Board::new();
+		Self::new_game_with_config(
+			BoardId::default(),
+			Vec::from([player1, player2]),
+			seed,
+			GameConfig::CLASSIC,
+		)
+		.expect("classic games are played by two players; qed")
+	}
+
+	/// Create a new game played on the given board with the given rules, by as many players as
+	/// the rules require. The players take their turns in the given order.
+	pub fn new_game_with_config(
+		board_id: BoardId,
+		players: Vec<Player>,
+		seed: Option<Seed>,
+		config: GameConfig,
+	) -> Result<GameState<Player>, GameError> {
+		if players.len() != config.players as usize {
+			return Err(GameError::InvalidPlayers)
+		}
+		let players = PerPlayer::try_from(players).map_err(|_| GameError::InvalidPlayers)?;
+		let first_player = players.first().cloned().ok_or(GameError::InvalidPlayers)?;
+
+		let mut board = Board::with_size(config.board_width, config.board_height);
 		let mut blocks = Vec::new();
-		let mut remaining_blocks = NUM_OF_BLOCKS;
 
 		let mut seed = seed.unwrap_or(INITIAL_SEED);
 
-		while remaining_blocks > 0 {
-			let (block_coordinates, new_seed) = Coordinates::random(seed);
+		// Blocks are drawn at random cells first, which some seeds cycle through without ever
+		// reaching enough free cells, so the draws are bounded.
+		let mut draws = config.blocks as u16 * BLOCK_DRAWS_PER_BLOCK;
+		while blocks.len() < config.blocks as usize && draws > 0 {
+			let (block_coordinates, new_seed) = Coordinates::random(seed, &board);
 			seed = new_seed;
+			draws -= 1;
 			if !blocks.contains(&block_coordinates) {
 				blocks.push(block_coordinates);
-				board.update_cell(&block_coordinates, Cell::Block);
-				remaining_blocks -= 1;
 			}
 		}
 
-		GameState {
+		// The blocks left are shuffled into the free cells the draws could have reached.
+		let mut free_cells = (0..config.board_height - 1)
+			.flat_map(|row| (0..config.board_width - 1).map(move |col| Coordinates::new(row, col)))
+			.filter(|coordinates| !blocks.contains(coordinates))
+			.collect::<Vec<_>>();
+		for index in 0..(config.blocks as usize - blocks.len()).min(free_cells.len()) {
+			seed = linear_congruential_generator(seed);
+			let pick = index + seed as usize % (free_cells.len() - index);
+			free_cells.swap(index, pick);
+			blocks.push(free_cells[index]);
+		}
+
+		blocks.iter().for_each(|block| board.update_cell(block, Cell::Block));
+
+		let bomb_energy = players
+			.iter()
+			.map(|player| (player.clone(), config.bomb_energy_per_player))
+			.collect::<Vec<_>>();
+		let bombs_placed = players.iter().map(|_| PlacedBombs::default()).collect::<Vec<_>>();
+
+		Ok(GameState {
+			board_id,
 			seed,
 			board,
 			winner: Default::default(),
-			next_player: player1.clone(),
-			players: [player1.clone(), player2.clone()],
-			bomb_energy: [(player1, BOMB_ENERGY_PER_PLAYER), (player2, BOMB_ENERGY_PER_PLAYER)],
-			bombs_placed: [BoundedVec::default(), BoundedVec::default()],
+			next_player: first_player,
+			config,
+			bomb_energy: PerPlayer::truncate_from(bomb_energy),
+			bombs_placed: PerPlayer::truncate_from(bombs_placed),
+			players,
 			last_move: Default::default(),
+			landed_stone: Default::default(),
+		})
+	}
+
+	/// List every move the player can make in the current state of the game.
+	pub fn legal_moves(game_state: &GameState<Player>, player: &Player) -> Vec<LegalMove> {
+		if game_state.is_finished() {
+			return Vec::new()
 		}
+
+		game_state.moves_of(player)
 	}
 
 	pub fn place_bomb(
-		mut game_state: GameState<Player>,
+		game_state: GameState<Player>,
 		player: Player,
 		coordinates: Coordinates,
 		salt: HashSalt,
+	) -> Result<GameState<Player>, GameError> {
+		let board_id = game_state.board_id;
+		Self::hide_bomb(
+			game_state,
+			player.clone(),
+			Self::hash_coordinates(coordinates, salt, &player, board_id),
+		)
+	}
+
+	/// Place a bomb given only its commitment, keeping its coordinates secret until it detonates
+	/// or hits a stone.
+	pub fn hide_bomb(
+		mut game_state: GameState<Player>,
+		player: Player,
+		coordinate_hash: HashedCoordinates,
 	) -> Result<GameState<Player>, GameError> {
 		Self::can_place_bomb(&game_state, &player)?;
 
 		let player_index = game_state.player_index(&player);
-		let coordinate_hash = Self::hash_coordinates(coordinates, salt);
-
 		if game_state.bombs_placed[player_index as usize].contains(&coordinate_hash) {
 			return Err(GameError::InvalidBombCoordinates)
 		}
@@ -416,6 +960,7 @@ impl<Player: PartialEq + Clone> Game<Player> {
 			.try_push(coordinate_hash)
 			.map_err(|_| GameError::NoMoreBombsAvailable)?;
 
+		game_state.landed_stone = None;
 		game_state.next_player = game_state.next_player().clone();
 
 		Ok(game_state)
@@ -429,8 +974,10 @@ impl<Player: PartialEq + Clone> Game<Player> {
 		power_level: PowerLevel,
 	) -> Result<GameState<Player>, GameError> {
 		let player_index = game_state.player_index(&player);
-		let coordinate_hash = Self::hash_coordinates(coordinates, salt);
+		let coordinate_hash =
+			Self::hash_coordinates(coordinates, salt, &player, game_state.board_id);
 
+		// Bombs placed by another player or on another board never match the commitment
 		if !game_state.bombs_placed[player_index as usize].contains(&coordinate_hash) {
 			return Err(GameError::InvalidBombCoordinates)
 		}
@@ -441,11 +988,55 @@ impl<Player: PartialEq + Clone> Game<Player> {
 		power_level.decrease_bomb_energy(&mut game_state, &player);
 
 		game_state.bombs_placed[player_index as usize].retain(|hash| hash != &coordinate_hash);
+		game_state.landed_stone = None;
+
+		// Who can move next is only known once the stones have settled
+		let mut game_state = Self::settle_after_explosion(game_state);
 		game_state.next_player = game_state.next_player().clone();
+		Ok(game_state)
+	}
 
+	/// Claim a hit of a bomb on the opponent stone that just landed on it, revealing the bomb.
+	/// Any player owning the bomb can claim it, in turn or not. The stone is blown up and the
+	/// turn stays with the next player.
+	pub fn claim_hit(
+		mut game_state: GameState<Player>,
+		player: Player,
+		coordinates: Coordinates,
+		salt: HashSalt,
+	) -> Result<GameState<Player>, GameError> {
+		Self::can_claim_hit(&game_state, &player)?;
+
+		let player_index = game_state.player_index(&player);
+		let coordinate_hash =
+			Self::hash_coordinates(coordinates, salt, &player, game_state.board_id);
+		if game_state.landed_stone != Some(coordinates) ||
+			!game_state.bombs_placed[player_index as usize].contains(&coordinate_hash)
+		{
+			return Err(GameError::InvalidBombCoordinates)
+		}
+
+		PowerLevel::One.explode(&mut game_state, &coordinates);
+		game_state.bombs_placed[player_index as usize].retain(|hash| hash != &coordinate_hash);
+		game_state.landed_stone = None;
+
+		// A hit claimed out of turn may leave the next player without a move to make
+		let mut game_state = Self::settle_after_explosion(game_state);
+		if game_state.turn_moves(&game_state.next_player).is_empty() {
+			game_state.next_player = game_state.next_player().clone();
+		}
 		Ok(game_state)
 	}
 
+	fn settle_after_explosion(mut game_state: GameState<Player>) -> GameState<Player> {
+		if game_state.config.gravity_after_explosions {
+			game_state.board.settle_stones();
+			// Settled stones may have formed new squares
+			game_state = Self::check_winner_player(game_state);
+		}
+		game_state
+	}
+
 	/// Drop stone. Called during play phase.
 	pub fn drop_stone(
 		mut game_state: GameState<Player>,
@@ -455,17 +1046,20 @@ impl<Player: PartialEq + Clone> Game<Player> {
 	) -> Result<GameState<Player>, GameError> {
 		Self::can_drop_stone(&game_state, &side, position, &player)?;
 		let player_index = game_state.player_index(&player);
+		let (board_width, board_height) = (game_state.board.width, game_state.board.height);
 		match side {
 			Side::North => {
 				let mut row = 0;
 				let mut stop = false;
-				while row < BOARD_HEIGHT && !stop {
+				while row < board_height && !stop {
 					let position = Coordinates::new(row, position);
 					match game_state.board.get_cell(&position) {
 						// The stone is placed at the end if it's empty.
 						Cell::Empty =>
-							if position.is_opposite_cell(side) {
-								game_state.board.update_cell(&position, Cell::Stone(player_index));
+							if position.is_opposite_cell(side, &game_state.board) {
+								game_state
+									.board
+									.update_cell(&position, Cell::Stone(player_index, side));
 								stop = true;
 							},
 						// The stone is placed in the position previous to a block.
@@ -473,7 +1067,7 @@ impl<Player: PartialEq + Clone> Game<Player> {
 							if row > 0 {
 								game_state.board.update_cell(
 									&Coordinates::new(position.row.saturating_sub(1), position.col),
-									Cell::Stone(player_index),
+									Cell::Stone(player_index, side),
 								);
 							} else {
 								return Err(GameError::InvalidStonePosition)
@@ -481,11 +1075,11 @@ impl<Player: PartialEq + Clone> Game<Player> {
 							stop = true;
 						},
 						// The stone is placed in the previous position of a stone.
-						Cell::Stone(_) => {
+						Cell::Stone(..) => {
 							if row > 0 {
 								game_state.board.update_cell(
 									&Coordinates::new(position.row.saturating_sub(1), position.col),
-									Cell::Stone(player_index),
+									Cell::Stone(player_index, side),
 								);
 							} else {
 								return Err(GameError::InvalidStonePosition)
@@ -497,23 +1091,25 @@ impl<Player: PartialEq + Clone> Game<Player> {
 				}
 			},
 			Side::East => {
-				let mut col = BOARD_WIDTH - 1;
+				let mut col = board_width - 1;
 
 				loop {
 					let position = Coordinates::new(position, col);
 					match game_state.board.get_cell(&position) {
 						// The stone is placed at the end if it's empty.
 						Cell::Empty =>
-							if position.is_opposite_cell(side) {
-								game_state.board.update_cell(&position, Cell::Stone(player_index));
+							if position.is_opposite_cell(side, &game_state.board) {
+								game_state
+									.board
+									.update_cell(&position, Cell::Stone(player_index, side));
 								break
 							},
 						// The stone is placed in the position previous to a block.
 						Cell::Block => {
-							if col < BOARD_WIDTH - 1 {
+							if col < board_width - 1 {
 								game_state.board.update_cell(
 									&Coordinates::new(position.row, position.col + 1),
-									Cell::Stone(player_index),
+									Cell::Stone(player_index, side),
 								);
 							} else {
 								return Err(GameError::InvalidStonePosition)
@@ -521,11 +1117,11 @@ impl<Player: PartialEq + Clone> Game<Player> {
 							break
 						},
 						// The stone is placed in the previous position of a stone.
-						Cell::Stone(_) => {
-							if col < BOARD_WIDTH - 1 {
+						Cell::Stone(..) => {
+							if col < board_width - 1 {
 								game_state.board.update_cell(
 									&Coordinates::new(position.row, position.col + 1),
-									Cell::Stone(player_index),
+									Cell::Stone(player_index, side),
 								);
 							} else {
 								return Err(GameError::InvalidStonePosition)
@@ -540,23 +1136,25 @@ impl<Player: PartialEq + Clone> Game<Player> {
 				}
 			},
 			Side::South => {
-				let mut row = BOARD_HEIGHT - 1;
+				let mut row = board_height - 1;
 
 				loop {
 					let position = Coordinates::new(row, position);
 					match game_state.board.get_cell(&position) {
 						// The stone is placed at the end if it's empty.
 						Cell::Empty =>
-							if position.is_opposite_cell(side) {
-								game_state.board.update_cell(&position, Cell::Stone(player_index));
+							if position.is_opposite_cell(side, &game_state.board) {
+								game_state
+									.board
+									.update_cell(&position, Cell::Stone(player_index, side));
 								break
 							},
 						// The stone is placed in the position previous to a block.
 						Cell::Block => {
-							if row < BOARD_HEIGHT - 1 {
+							if row < board_height - 1 {
 								game_state.board.update_cell(
 									&Coordinates::new(position.row + 1, position.col),
-									Cell::Stone(player_index),
+									Cell::Stone(player_index, side),
 								);
 							} else {
 								return Err(GameError::InvalidStonePosition)
@@ -564,11 +1162,11 @@ impl<Player: PartialEq + Clone> Game<Player> {
 							break
 						},
 						// The stone is placed in the previous position of a stone.
-						Cell::Stone(_) => {
-							if row < BOARD_HEIGHT - 1 {
+						Cell::Stone(..) => {
+							if row < board_height - 1 {
 								game_state.board.update_cell(
 									&Coordinates::new(position.row + 1, position.col),
-									Cell::Stone(player_index),
+									Cell::Stone(player_index, side),
 								);
 							} else {
 								return Err(GameError::InvalidStonePosition)
@@ -586,13 +1184,15 @@ impl<Player: PartialEq + Clone> Game<Player> {
 			Side::West => {
 				let mut col = 0;
 				let mut stop = false;
-				while col < BOARD_WIDTH && !stop {
+				while col < board_width && !stop {
 					let position = Coordinates::new(position, col);
 					match game_state.board.get_cell(&position) {
 						// The stone is placed at the end if it's empty.
 						Cell::Empty =>
-							if position.is_opposite_cell(side) {
-								game_state.board.update_cell(&position, Cell::Stone(player_index));
+							if position.is_opposite_cell(side, &game_state.board) {
+								game_state
+									.board
+									.update_cell(&position, Cell::Stone(player_index, side));
 								stop = true;
 							},
 						// The stone is placed in the position previous to a block.
@@ -600,7 +1200,7 @@ impl<Player: PartialEq + Clone> Game<Player> {
 							if col > 0 {
 								game_state.board.update_cell(
 									&Coordinates::new(position.row, position.col.saturating_sub(1)),
-									Cell::Stone(player_index),
+									Cell::Stone(player_index, side),
 								);
 							} else {
 								return Err(GameError::InvalidStonePosition)
@@ -608,11 +1208,11 @@ impl<Player: PartialEq + Clone> Game<Player> {
 							stop = true;
 						},
 						// The stone is placed in the previous position of a stone.
-						Cell::Stone(_) => {
-							if col < BOARD_WIDTH - 1 {
+						Cell::Stone(..) => {
+							if col > 0 {
 								game_state.board.update_cell(
 									&Coordinates::new(position.row, position.col.saturating_sub(1)),
-									Cell::Stone(player_index),
+									Cell::Stone(player_index, side),
 								);
 							} else {
 								return Err(GameError::InvalidStonePosition)
@@ -626,6 +1226,7 @@ impl<Player: PartialEq + Clone> Game<Player> {
 		}
 
 		game_state.last_move = Some(LastMove::new(player, side, position));
+		game_state.landed_stone = side.landing_cell(position, &game_state.board);
 		game_state.next_player = game_state.next_player().clone();
 		game_state = Self::check_winner_player(game_state);
 
@@ -637,36 +1238,24 @@ impl<Player: PartialEq + Clone> Game<Player> {
 			return game_state
 		}
 
-		let board = &game_state.board;
-		let mut squares = [0; NUM_OF_PLAYERS];
-
-		for row in 0..BOARD_HEIGHT - 1 {
-			for col in 0..BOARD_WIDTH - 1 {
-				let cell = board.get_cell(&Coordinates::new(row, col));
-				if let Cell::Stone(player_index) = cell {
-					if cell == board.get_cell(&Coordinates::new(row, col + 1)) &&
-						cell == board.get_cell(&Coordinates::new(row + 1, col)) &&
-						cell == board.get_cell(&Coordinates::new(row + 1, col + 1))
-					{
-						squares[player_index as usize] += 1;
-						if squares[player_index as usize] >= 3 {
-							let winner = game_state.players[player_index as usize].clone();
-							game_state.winner = Some(winner);
-							break
-						}
-					}
-				}
-			}
+		let squares_to_win = game_state.config.squares_to_win;
+		if let Some(player_index) =
+			game_state.squares().iter().position(|squares| *squares >= squares_to_win)
+		{
+			game_state.winner = Some(game_state.players[player_index].clone());
 		}
 
 		game_state
 	}
 
-	fn hash_coordinates(coordinates: Coordinates, salt: HashSalt) -> HashedCoordinates {
-		let mut hashed_coordinates = salt;
-		hashed_coordinates.0[30] = coordinates.row;
-		hashed_coordinates.0[31] = coordinates.col;
-		let choice_hashed = blake2_256(hashed_coordinates.as_bytes());
-		choice_hashed.using_encoded(BlakeTwo256::hash)
+	/// Commitment to a bomb placed by the player on the board, revealed by its coordinates and
+	/// salt when detonated.
+	pub fn hash_coordinates(
+		coordinates: Coordinates,
+		salt: HashSalt,
+		player: &Player,
+		board_id: BoardId,
+	) -> HashedCoordinates {
+		H256(blake2_256(&(coordinates, salt, player, board_id).encode()))
 	}
 }
@@ -19,6 +19,15 @@ use crate::dot4gravity::*;
 const ALICE: u8 = 11;
 const BOB: u8 = 22;
 const CHARLIE: u8 = 33;
+const DAVE: u8 = 44;
+
+const BOARD_ID: BoardId = 7;
+
+const BOARD_WIDTH: u8 = GameConfig::CLASSIC.board_width;
+const BOARD_HEIGHT: u8 = GameConfig::CLASSIC.board_height;
+const NUM_OF_BLOCKS: u8 = GameConfig::CLASSIC.blocks;
+const BOMB_AMOUNT_PER_PLAYER: u8 = GameConfig::CLASSIC.bombs_per_player;
+const BOMB_ENERGY_PER_PLAYER: u8 = GameConfig::CLASSIC.bomb_energy_per_player;
 
 #[test]
 fn should_create_a_new_board() {
@@ -35,8 +44,8 @@ fn should_create_a_new_board() {
 	}
 
 	let board = Board::new();
-	assert_eq!(board.cells.len() as u8, BOARD_HEIGHT);
-	assert_eq!(board.cells[0].len() as u8, BOARD_WIDTH);
+	assert_eq!(board.height(), BOARD_HEIGHT);
+	assert_eq!(board.width(), BOARD_WIDTH);
 	assert!(is_empty(&board))
 }
 
@@ -50,6 +59,51 @@ fn board_cell_can_be_changed() {
 	assert_eq!(board.get_cell(&coords), Cell::Block, "Cell should had changed.");
 }
 
+#[test]
+fn board_is_displayed_row_by_row() {
+	let mut board = Board::new();
+	board.update_cell(&Coordinates::new(0, 0), Cell::Block);
+	board.update_cell(&Coordinates::new(0, 9), Cell::Stone(1, Side::North));
+	board.update_cell(&Coordinates::new(9, 4), Cell::Stone(0, Side::North));
+
+	let rendered = board.to_string();
+	let rows = rendered.lines().collect::<Vec<_>>();
+	assert_eq!(rows.len() as u8, BOARD_HEIGHT);
+	assert_eq!(rows[0], "# . . . . . . . . 1");
+	assert_eq!(rows[5], ". . . . . . . . . .");
+	assert_eq!(rows[9], ". . . . 0 . . . . .");
+}
+
+#[test]
+fn game_state_is_displayed_with_its_players_above_the_board() {
+	let mut state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
+	state.board = Board::new();
+	state = Game::place_bomb(state, ALICE, Coordinates::new(1, 1), HashSalt::default()).unwrap();
+
+	let rendered = state.to_string();
+	let lines = rendered.lines().collect::<Vec<_>>();
+	assert_eq!(lines[0], "0: 11 with 1 bombs placed and 5 bomb energy");
+	assert_eq!(lines[1], "1: 22 with 0 bombs placed and 5 bomb energy");
+	assert_eq!(lines[2], "Next player is 22");
+	assert_eq!(lines[3..].join("\n") + "\n", state.board.to_string());
+
+	state.winner = Some(BOB);
+	assert_eq!(state.to_string().lines().nth(2), Some("Won by 22"));
+}
+
+#[test]
+fn game_state_round_trips_through_json() {
+	let state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
+	let state =
+		Game::place_bomb(state, ALICE, Coordinates::new(1, 1), HashSalt::repeat_byte(7)).unwrap();
+
+	let json = serde_json::to_value(&state).unwrap();
+	assert_eq!(json["bomb_energy"], serde_json::json!([[ALICE, 5], [BOB, 5]]));
+	assert_eq!(json["bombs_placed"][0].as_array().map(Vec::len), Some(1));
+	assert_eq!(json["board"]["cells"][0][0], serde_json::json!("Empty"));
+	assert_eq!(serde_json::from_value::<GameState<u8>>(json).unwrap(), state);
+}
+
 #[test]
 fn should_create_new_game() {
 	let game_state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
@@ -57,7 +111,7 @@ fn should_create_new_game() {
 	assert_eq!(game_state.seed, computed_from_initial_seed);
 	assert_eq!(game_state.winner, None, "No player should have won yet");
 	assert_eq!(game_state.next_player, ALICE);
-	assert_eq!(game_state.bomb_energy.len(), NUM_OF_PLAYERS);
+	assert_eq!(game_state.bomb_energy.len(), 2);
 	assert_eq!(game_state.get_bomb_energy_for(&ALICE), Some(BOMB_ENERGY_PER_PLAYER),);
 	assert_eq!(game_state.get_bomb_energy_for(&BOB), Some(BOMB_ENERGY_PER_PLAYER),);
 	assert!(game_state.is_player_in_game(&ALICE), "Player Alice should be in the game");
@@ -93,6 +147,76 @@ fn should_create_new_game_with_random_blocks() {
 	}
 }
 
+#[test]
+fn should_create_new_game_with_config() {
+	let config = GameConfig::QUICK;
+	let state =
+		Game::new_game_with_config(BOARD_ID, vec![ALICE, BOB], Some(INITIAL_SEED), config).unwrap();
+	assert_eq!(state.config, config);
+	assert_eq!((state.board.width(), state.board.height()), (8, 8));
+	assert_eq!(state.get_bomb_energy_for(&ALICE), Some(config.bomb_energy_per_player));
+	let blocks = (0..8)
+		.flat_map(|row| (0..8).map(move |col| Coordinates::new(row, col)))
+		.filter(|position| state.board.get_cell(position) == Cell::Block)
+		.count();
+	assert_eq!(blocks, config.blocks as usize, "\n{}", state.board);
+
+	assert!(Game::legal_moves(&state, &ALICE).iter().all(|legal_move| match legal_move {
+		LegalMove::DropStone(_, position) => *position < 8,
+		_ => true,
+	}));
+	assert_eq!(
+		Game::drop_stone(state, ALICE, Side::North, 8),
+		Err(GameError::InvalidStonePosition)
+	);
+}
+
+#[test]
+fn a_player_wins_with_the_squares_required_by_the_config() {
+	let mut state = Game::new_game_with_config(
+		BOARD_ID,
+		vec![ALICE, BOB],
+		Some(INITIAL_SEED),
+		GameConfig::QUICK,
+	)
+	.unwrap();
+	state.board = Board::with_size(8, 8);
+	for (row, col) in [(6, 0), (6, 1), (7, 0), (7, 1), (7, 2)] {
+		state
+			.board
+			.update_cell(&Coordinates::new(row, col), Cell::Stone(0, Side::North));
+	}
+
+	let mut classic_rules = state.clone();
+	classic_rules.config.squares_to_win = GameConfig::CLASSIC.squares_to_win;
+	let classic_rules = Game::drop_stone(classic_rules, ALICE, Side::North, 2).unwrap();
+	assert_eq!(classic_rules.winner, None, "\n{}", classic_rules);
+
+	let state = Game::drop_stone(state, ALICE, Side::North, 2).unwrap();
+	assert_eq!(state.board.get_cell(&Coordinates::new(6, 2)), Cell::Stone(0, Side::North));
+	assert_eq!(state.winner, Some(ALICE), "\n{}", state);
+}
+
+#[test]
+fn should_create_new_game_with_all_blocks_for_a_seed_repeating_the_same_cell() {
+	// the generator maps this seed onto itself, drawing the same cell over and over
+	let seed = MODULUS - 1;
+	let (coordinates, next_seed) = Coordinates::random(seed, &Board::new());
+	assert_eq!(next_seed, seed);
+	assert_eq!(Coordinates::random(next_seed, &Board::new()).0, coordinates);
+
+	let game_state = Game::new_game(ALICE, BOB, Some(seed));
+	let blocks = game_state
+		.board
+		.cells
+		.iter()
+		.flatten()
+		.filter(|cell| **cell == Cell::Block)
+		.count();
+	assert_eq!(blocks, GameConfig::CLASSIC.blocks as usize);
+	assert_eq!(game_state.board.get_cell(&coordinates), Cell::Block);
+}
+
 #[test]
 fn should_create_new_game_with_deterministic_blocks_with_fixed_seed() {
 	let seed = 7357;
@@ -103,6 +227,14 @@ fn should_create_new_game_with_deterministic_blocks_with_fixed_seed() {
 	}
 }
 
+#[test]
+fn should_create_new_game_with_different_blocks_for_seeds_beyond_the_modulus() {
+	let board_of = |seed| Game::new_game(ALICE, BOB, Some(seed)).board;
+	assert_ne!(board_of(Seed::MAX), board_of(Seed::MAX - 1));
+	assert_ne!(board_of(1_609_736_203), board_of(477_551_327));
+	assert_eq!(board_of(Seed::MAX), board_of(Seed::MAX % MODULUS));
+}
+
 #[test]
 fn a_player_cannot_drop_a_stone_out_of_turn() {
 	let state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
@@ -128,7 +260,7 @@ fn a_player_cannot_place_more_bombs_than_the_limit() {
 		game_state = Game::place_bomb(
 			game_state,
 			ALICE,
-			Coordinates { row: 0, col: i as u8 },
+			Coordinates { row: 0, col: i },
 			HashSalt::from_low_u64_le(3453),
 		)
 		.expect("Bomb should be placed");
@@ -224,6 +356,67 @@ fn a_player_cannot_detonate_a_bomb_with_the_wrong_coordinates() {
 	)
 }
 
+#[test]
+fn bomb_commitments_depend_on_the_whole_salt() {
+	let coordinates = Coordinates::new(1, 2);
+	let salt = HashSalt::repeat_byte(9);
+	let mut other_salt = salt;
+	other_salt.0[30] = 0;
+	other_salt.0[31] = 0;
+
+	assert_ne!(
+		Game::hash_coordinates(coordinates, salt, &ALICE, BOARD_ID),
+		Game::hash_coordinates(coordinates, other_salt, &ALICE, BOARD_ID)
+	);
+	assert_ne!(
+		Game::hash_coordinates(coordinates, salt, &ALICE, BOARD_ID),
+		Game::hash_coordinates(coordinates, salt, &BOB, BOARD_ID)
+	);
+	assert_ne!(
+		Game::hash_coordinates(coordinates, salt, &ALICE, BOARD_ID),
+		Game::hash_coordinates(coordinates, salt, &ALICE, BOARD_ID + 1)
+	);
+}
+
+#[test]
+fn a_player_cannot_detonate_a_bomb_committed_on_another_board() {
+	let coordinates = Coordinates::new(0, 0);
+	let salt = HashSalt::from_low_u64_le(3453);
+	let state = Game::new_game_with_config(
+		BOARD_ID,
+		vec![ALICE, BOB],
+		Some(INITIAL_SEED),
+		GameConfig::CLASSIC,
+	)
+	.unwrap();
+	let state = Game::place_bomb(state, ALICE, coordinates, salt).unwrap();
+
+	let mut other_board = Game::new_game_with_config(
+		BOARD_ID + 1,
+		vec![ALICE, BOB],
+		Some(INITIAL_SEED),
+		GameConfig::CLASSIC,
+	)
+	.unwrap();
+	other_board.bombs_placed = state.bombs_placed.clone();
+	assert_eq!(
+		Game::detonate_bomb(other_board, ALICE, coordinates, salt, PowerLevel::One),
+		Err(GameError::InvalidBombCoordinates)
+	);
+
+	let mut state = state;
+	let (alice_index, bob_index) = (state.player_index(&ALICE), state.player_index(&BOB));
+	state.bombs_placed[bob_index as usize] = state.bombs_placed[alice_index as usize].clone();
+	state.next_player = BOB;
+	assert_eq!(
+		Game::detonate_bomb(state.clone(), BOB, coordinates, salt, PowerLevel::One),
+		Err(GameError::InvalidBombCoordinates)
+	);
+
+	state.next_player = ALICE;
+	assert!(Game::detonate_bomb(state, ALICE, coordinates, salt, PowerLevel::One).is_ok());
+}
+
 #[test]
 fn player_turn_changes_after_dropping_stone() {
 	let mut state = Game::new_game(CHARLIE, BOB, Some(INITIAL_SEED));
@@ -265,7 +458,7 @@ fn a_stone_dropped_on_a_stone() {
 	let (alice_index, bob_index) = (state.player_index(&ALICE), state.player_index(&BOB));
 
 	let o = Cell::Empty;
-	let x = Cell::Stone(bob_index);
+	let x = Cell::Stone(bob_index, Side::North);
 	let cells = [
 		[o, x, o, o, o, o, o, o, o, o],
 		[o, o, o, o, o, o, o, o, o, o],
@@ -282,8 +475,14 @@ fn a_stone_dropped_on_a_stone() {
 	state.board.cells = cells;
 
 	let state = Game::drop_stone(state, ALICE, Side::West, 0).unwrap();
-	assert_eq!(state.board.get_cell(&Coordinates { row: 0, col: 0 }), Cell::Stone(alice_index));
-	assert_eq!(state.board.get_cell(&Coordinates { row: 0, col: 1 }), Cell::Stone(bob_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 0, col: 0 }),
+		Cell::Stone(alice_index, Side::West)
+	);
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 0, col: 1 }),
+		Cell::Stone(bob_index, Side::North)
+	);
 }
 
 #[test]
@@ -292,7 +491,7 @@ fn a_stone_cannot_be_dropped_at_bounds() {
 
 	let mut state_with_stones_at_bounds = state.clone();
 	let o = Cell::Empty;
-	let x = Cell::Stone(state_with_stones_at_bounds.player_index(&BOB));
+	let x = Cell::Stone(state_with_stones_at_bounds.player_index(&BOB), Side::North);
 	state_with_stones_at_bounds.board.cells = [
 		[x, x, x, x, x, x, x, x, x, x],
 		[x, o, o, o, o, o, o, o, o, x],
@@ -370,11 +569,20 @@ fn a_stone_dropped_from_north_side_should_move_until_it_reaches_an_obstacle() {
 
 	let state = Game::drop_stone(state, ALICE, Side::North, 0).unwrap();
 	let (alice_index, bob_index) = (state.player_index(&ALICE), state.player_index(&BOB));
-	assert_eq!(state.board.get_cell(&Coordinates { row: 9, col: 0 }), Cell::Stone(alice_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 9, col: 0 }),
+		Cell::Stone(alice_index, Side::North)
+	);
 	let state = Game::drop_stone(state, BOB, Side::North, 1).unwrap();
-	assert_eq!(state.board.get_cell(&Coordinates { row: 8, col: 1 }), Cell::Stone(bob_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 8, col: 1 }),
+		Cell::Stone(bob_index, Side::North)
+	);
 	let state = Game::drop_stone(state, ALICE, Side::North, 2).unwrap();
-	assert_eq!(state.board.get_cell(&Coordinates { row: 0, col: 2 }), Cell::Stone(alice_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 0, col: 2 }),
+		Cell::Stone(alice_index, Side::North)
+	);
 	assert_eq!(
 		Game::drop_stone(state, BOB, Side::North, 3).unwrap_err(),
 		GameError::InvalidStonePosition
@@ -404,11 +612,20 @@ fn a_stone_dropped_from_south_side_should_move_until_it_reaches_an_obstacle() {
 	state.board.cells = cells;
 
 	let state = Game::drop_stone(state, ALICE, Side::South, 0).unwrap();
-	assert_eq!(state.board.get_cell(&Coordinates { row: 0, col: 0 }), Cell::Stone(alice_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 0, col: 0 }),
+		Cell::Stone(alice_index, Side::South)
+	);
 	let state = Game::drop_stone(state, BOB, Side::South, 1).unwrap();
-	assert_eq!(state.board.get_cell(&Coordinates { row: 1, col: 1 }), Cell::Stone(bob_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 1, col: 1 }),
+		Cell::Stone(bob_index, Side::South)
+	);
 	let state = Game::drop_stone(state, ALICE, Side::South, 2).unwrap();
-	assert_eq!(state.board.get_cell(&Coordinates { row: 9, col: 2 }), Cell::Stone(alice_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 9, col: 2 }),
+		Cell::Stone(alice_index, Side::South)
+	);
 	assert_eq!(
 		Game::drop_stone(state, BOB, Side::South, 3).unwrap_err(),
 		GameError::InvalidStonePosition
@@ -438,11 +655,20 @@ fn a_stone_dropped_from_east_side_should_move_until_it_reaches_an_obstacle() {
 	state.board.cells = cells;
 
 	let state = Game::drop_stone(state, ALICE, Side::East, 0).unwrap();
-	assert_eq!(state.board.get_cell(&Coordinates { row: 0, col: 0 }), Cell::Stone(alice_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 0, col: 0 }),
+		Cell::Stone(alice_index, Side::East)
+	);
 	let state = Game::drop_stone(state, BOB, Side::East, 1).unwrap();
-	assert_eq!(state.board.get_cell(&Coordinates { row: 1, col: 1 }), Cell::Stone(bob_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 1, col: 1 }),
+		Cell::Stone(bob_index, Side::East)
+	);
 	let state = Game::drop_stone(state, ALICE, Side::East, 2).unwrap();
-	assert_eq!(state.board.get_cell(&Coordinates { row: 2, col: 9 }), Cell::Stone(alice_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 2, col: 9 }),
+		Cell::Stone(alice_index, Side::East)
+	);
 	assert_eq!(
 		Game::drop_stone(state, BOB, Side::East, 3).unwrap_err(),
 		GameError::InvalidStonePosition
@@ -472,11 +698,20 @@ fn a_stone_dropped_from_west_side_should_move_until_it_reaches_an_obstacle() {
 
 	let state = Game::drop_stone(state, ALICE, Side::West, 0).unwrap();
 	let (alice_index, bob_index) = (state.player_index(&ALICE), state.player_index(&BOB));
-	assert_eq!(state.board.get_cell(&Coordinates { row: 0, col: 9 }), Cell::Stone(alice_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 0, col: 9 }),
+		Cell::Stone(alice_index, Side::West)
+	);
 	let state = Game::drop_stone(state, BOB, Side::West, 1).unwrap();
-	assert_eq!(state.board.get_cell(&Coordinates { row: 1, col: 8 }), Cell::Stone(bob_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 1, col: 8 }),
+		Cell::Stone(bob_index, Side::West)
+	);
 	let state = Game::drop_stone(state, ALICE, Side::West, 2).unwrap();
-	assert_eq!(state.board.get_cell(&Coordinates { row: 2, col: 0 }), Cell::Stone(alice_index));
+	assert_eq!(
+		state.board.get_cell(&Coordinates { row: 2, col: 0 }),
+		Cell::Stone(alice_index, Side::West)
+	);
 	assert_eq!(
 		Game::drop_stone(state, BOB, Side::West, 3).unwrap_err(),
 		GameError::InvalidStonePosition
@@ -488,7 +723,7 @@ fn a_player_wins_when_has_stones_in_three_squares() {
 	let mut state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
 	let alice_index = state.player_index(&ALICE);
 	let o = Cell::Empty;
-	let s = Cell::Stone(alice_index);
+	let s = Cell::Stone(alice_index, Side::North);
 	state.board.cells = [
 		[o, o, o, o, o, o, o, o, o, o],
 		[o, o, s, s, o, o, o, o, o, o],
@@ -511,7 +746,7 @@ fn a_player_wins_when_has_stones_in_three_squares_with_overlap() {
 	let mut state = Game::new_game(CHARLIE, BOB, Some(INITIAL_SEED));
 	let winner_index = state.player_index(&BOB);
 	let o = Cell::Empty;
-	let w = Cell::Stone(winner_index);
+	let w = Cell::Stone(winner_index, Side::North);
 	state.board.cells = [
 		[o, o, o, o, o, o, o, o, o, o],
 		[o, o, o, o, o, o, o, o, o, o],
@@ -534,8 +769,8 @@ fn no_player_wins_if_stones_are_not_in_four_squares() {
 	let mut state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
 	let o = Cell::Empty;
 	let b = Cell::Block;
-	let r = Cell::Stone(state.player_index(&ALICE));
-	let m = Cell::Stone(state.player_index(&BOB));
+	let r = Cell::Stone(state.player_index(&ALICE), Side::North);
+	let m = Cell::Stone(state.player_index(&BOB), Side::North);
 	state.board.cells = [
 		[o, r, o, o, o, o, o, o, m, o],
 		[m, o, o, o, o, m, o, o, o, o],
@@ -636,11 +871,11 @@ fn should_play_a_game() {
 	// bob drops a stone back
 	state = Game::drop_stone(state, BOB, Side::South, 2).unwrap();
 
-	assert!(state.winner.is_none());
-	let x = Cell::Stone(state.player_index(&ALICE));
-	let y = Cell::Stone(state.player_index(&BOB));
+	assert!(state.winner.is_none(), "No player should have won yet:\n{}", state);
+	let x = Cell::Stone(state.player_index(&ALICE), Side::North);
+	let y = Cell::Stone(state.player_index(&BOB), Side::North);
 	assert_eq!(
-		state.board.cells,
+		cells_ignoring_sides(&state.board),
 		[
 			[o, o, o, o, o, x, o, o, b, o],
 			[b, o, o, o, o, x, x, o, x, x],
@@ -652,11 +887,563 @@ fn should_play_a_game() {
 			[o, o, y, o, o, o, o, o, o, o],
 			[o, o, o, o, y, y, y, y, o, o],
 			[o, o, o, o, o, o, o, o, o, o],
-		]
+		],
+		"Unexpected board:\n{}",
+		state.board
 	);
 
 	// trigger winning condition and check winner
 	state = Game::drop_stone(state, ALICE, Side::North, 6).unwrap();
-	assert!(state.winner.is_some());
+	assert!(state.winner.is_some(), "Alice should have won:\n{}", state);
 	assert_eq!(state.winner.unwrap(), ALICE);
 }
+
+/// Cells of the board with every stone taken as dropped from the north, to compare stones by
+/// their owner only.
+fn cells_ignoring_sides(board: &Board) -> [[Cell; BOARD_WIDTH as usize]; BOARD_HEIGHT as usize] {
+	board.cells.map(|row| {
+		row.map(|cell| match cell {
+			Cell::Stone(player_index, _) => Cell::Stone(player_index, Side::North),
+			cell => cell,
+		})
+	})
+}
+
+fn fill_edges_with_stones(state: &mut GameState<u8>) {
+	for row in 0..BOARD_HEIGHT {
+		for col in 0..BOARD_WIDTH {
+			let position = Coordinates::new(row, col);
+			if row == 0 || col == 0 || row == BOARD_HEIGHT - 1 || col == BOARD_WIDTH - 1 {
+				state.board.update_cell(&position, Cell::Stone((row + col) % 2, Side::North));
+			} else {
+				state.board.update_cell(&position, Cell::Empty);
+			}
+		}
+	}
+}
+
+fn can_drop_a_stone(state: &GameState<u8>, player: &u8) -> bool {
+	Game::legal_moves(state, player)
+		.iter()
+		.any(|legal_move| matches!(legal_move, LegalMove::DropStone(..)))
+}
+
+#[test]
+fn a_game_is_drawn_when_the_board_is_blocked_and_no_bomb_energy_is_left() {
+	let mut state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
+	fill_edges_with_stones(&mut state);
+	state.board.update_cell(&Coordinates::new(0, 5), Cell::Empty);
+	state.board.update_cell(&Coordinates::new(1, 5), Cell::Block);
+	state.decrease_bomb_energy_for(&ALICE, BOMB_ENERGY_PER_PLAYER);
+	state.decrease_bomb_energy_for(&BOB, BOMB_ENERGY_PER_PLAYER);
+	assert!(!state.is_draw(), "A stone can still be dropped");
+
+	state = Game::drop_stone(state, ALICE, Side::North, 5).unwrap();
+	assert!(!can_drop_a_stone(&state, &BOB), "No stone should be droppable:\n{}", state.board);
+	assert!(state.winner.is_none());
+	assert!(state.is_draw(), "The game should be drawn:\n{}", state);
+	assert_eq!(
+		Game::place_bomb(state, BOB, Coordinates::new(5, 5), HashSalt::default()),
+		Err(GameError::GameAlreadyFinished)
+	);
+}
+
+#[test]
+fn a_game_is_not_drawn_while_the_next_player_can_still_move() {
+	let mut state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
+	fill_edges_with_stones(&mut state);
+	assert!(!can_drop_a_stone(&state, &ALICE));
+	assert!(!state.is_draw(), "Bombs can still clear the board");
+
+	state.decrease_bomb_energy_for(&BOB, BOMB_ENERGY_PER_PLAYER);
+	assert!(!state.is_draw(), "Alice can still clear the board");
+
+	state.winner = Some(ALICE);
+	assert!(!state.is_draw(), "A won game is not a draw");
+}
+
+#[test]
+fn players_without_a_legal_move_are_skipped_until_nobody_can_move() {
+	let mut state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
+	fill_edges_with_stones(&mut state);
+	state.decrease_bomb_energy_for(&ALICE, BOMB_ENERGY_PER_PLAYER);
+	state.next_player = BOB;
+	assert!(!state.is_draw(), "Bob can still move although Alice cannot");
+
+	state = Game::place_bomb(state, BOB, Coordinates::new(5, 5), HashSalt::default()).unwrap();
+	assert_eq!(state.next_player, BOB, "Alice should be skipped");
+	assert_eq!(Game::legal_moves(&state, &ALICE), vec![]);
+	assert!(!state.is_draw());
+
+	state.decrease_bomb_energy_for(&BOB, BOMB_ENERGY_PER_PLAYER);
+	assert!(state.is_draw(), "Nobody can move anymore:\n{}", state);
+	assert_eq!(
+		Game::place_bomb(state, BOB, Coordinates::new(4, 4), HashSalt::default()),
+		Err(GameError::GameAlreadyFinished)
+	);
+}
+
+#[test]
+fn a_bomb_cannot_be_placed_without_bomb_energy_to_detonate_it() {
+	let mut state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
+	state.decrease_bomb_energy_for(&ALICE, BOMB_ENERGY_PER_PLAYER);
+	assert!(!Game::legal_moves(&state, &ALICE).contains(&LegalMove::PlaceBomb));
+	assert_eq!(
+		Game::place_bomb(state, ALICE, Coordinates::new(5, 5), HashSalt::default()),
+		Err(GameError::InsufficientBombEnergy)
+	);
+}
+
+#[test]
+fn legal_moves_should_match_the_stones_accepted_by_drop_stone() {
+	let mut seed = INITIAL_SEED;
+	for _ in 0..50 {
+		let mut state = Game::new_game(ALICE, BOB, Some(seed));
+		// scatter stones of both players over the blocks of the board
+		for stone in 0..40 {
+			let (coordinates, new_seed) = Coordinates::random(state.seed, &state.board);
+			state.seed = new_seed;
+			if state.board.get_cell(&coordinates) == Cell::Empty {
+				state.board.update_cell(&coordinates, Cell::Stone(stone % 2, Side::North));
+			}
+		}
+		seed = state.seed;
+
+		let legal_moves = Game::legal_moves(&state, &ALICE);
+		for side in Side::ALL {
+			for position in 0..side.positions(&state.board) {
+				let is_legal = legal_moves.contains(&LegalMove::DropStone(side, position));
+				let is_accepted = Game::drop_stone(state.clone(), ALICE, side, position).is_ok();
+				assert_eq!(is_legal, is_accepted, "{:?} {} on\n{}", side, position, state);
+			}
+		}
+		assert!(
+			legal_moves.iter().all(|legal_move| match legal_move {
+				LegalMove::DropStone(side, position) => position < &side.positions(&state.board),
+				_ => true,
+			}),
+			"Only positions inside the board should be legal"
+		);
+		assert!(Game::legal_moves(&state, &BOB).is_empty(), "Bob should wait for his turn");
+	}
+
+	// the only obstacle in the row is a stone on the opposite edge
+	let mut state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
+	state.board = Board::new();
+	state
+		.board
+		.update_cell(&Coordinates::new(3, BOARD_WIDTH - 1), Cell::Stone(1, Side::North));
+	assert!(Game::legal_moves(&state, &ALICE).contains(&LegalMove::DropStone(Side::West, 3)));
+	let state = Game::drop_stone(state, ALICE, Side::West, 3).unwrap();
+	assert_eq!(
+		state.board.get_cell(&Coordinates::new(3, BOARD_WIDTH - 2)),
+		Cell::Stone(0, Side::West)
+	);
+	assert_eq!(
+		state.board.get_cell(&Coordinates::new(3, BOARD_WIDTH - 1)),
+		Cell::Stone(1, Side::North)
+	);
+
+	// a stone on the edge the stone is dropped from leaves no room for it
+	let mut state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
+	state.board = Board::new();
+	state.board.update_cell(&Coordinates::new(3, 0), Cell::Stone(1, Side::North));
+	assert!(!Game::legal_moves(&state, &ALICE).contains(&LegalMove::DropStone(Side::West, 3)));
+	assert_eq!(Game::drop_stone(state, ALICE, Side::West, 3), Err(GameError::InvalidStonePosition));
+}
+
+#[test]
+fn legal_moves_should_list_bomb_placements_and_detonations() {
+	let salt = HashSalt::from_low_u64_be(1);
+	let bomb = Coordinates::new(4, 4);
+	let mut state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
+	assert!(Game::legal_moves(&state, &ALICE).contains(&LegalMove::PlaceBomb));
+	assert!(!Game::legal_moves(&state, &ALICE)
+		.iter()
+		.any(|legal_move| matches!(legal_move, LegalMove::DetonateBomb(_))));
+
+	state = Game::place_bomb(state, ALICE, bomb, salt).unwrap();
+	state = Game::place_bomb(state, BOB, bomb, salt).unwrap();
+	let legal_moves = Game::legal_moves(&state, &ALICE);
+	assert!(legal_moves.contains(&LegalMove::PlaceBomb));
+	assert!(legal_moves.contains(&LegalMove::DetonateBomb(PowerLevel::One)));
+	assert!(legal_moves.contains(&LegalMove::DetonateBomb(PowerLevel::Two)));
+	assert!(legal_moves.contains(&LegalMove::DetonateBomb(PowerLevel::Three)));
+
+	state.decrease_bomb_energy_for(&ALICE, BOMB_ENERGY_PER_PLAYER - 2);
+	let legal_moves = Game::legal_moves(&state, &ALICE);
+	assert!(legal_moves.contains(&LegalMove::DetonateBomb(PowerLevel::Two)));
+	assert!(!legal_moves.contains(&LegalMove::DetonateBomb(PowerLevel::Three)));
+
+	state.winner = Some(BOB);
+	assert!(Game::legal_moves(&state, &ALICE).is_empty(), "No moves after the game is over");
+	assert!(Game::legal_moves(&state, &CHARLIE).is_empty(), "Charlie is not playing");
+}
+
+fn assert_explosion_is_clipped_to_the_board(config: GameConfig, epicenter: Coordinates) {
+	for power_level in [PowerLevel::One, PowerLevel::Two, PowerLevel::Three] {
+		let mut state =
+			Game::new_game_with_config(BOARD_ID, vec![ALICE, BOB], Some(INITIAL_SEED), config)
+				.unwrap();
+		for row in 0..config.board_height {
+			for col in 0..config.board_width {
+				state
+					.board
+					.update_cell(&Coordinates::new(row, col), Cell::Stone(0, Side::North));
+			}
+		}
+
+		power_level.explode(&mut state, &epicenter);
+
+		for row in 0..config.board_height {
+			for col in 0..config.board_width {
+				let row_distance = row.abs_diff(epicenter.row);
+				let col_distance = col.abs_diff(epicenter.col);
+				let is_hit = match power_level {
+					PowerLevel::One => row_distance == 0 && col_distance == 0,
+					PowerLevel::Two => row_distance + col_distance <= 1,
+					PowerLevel::Three => row_distance <= 1 && col_distance <= 1,
+				};
+				let expected = if is_hit { Cell::Empty } else { Cell::Stone(0, Side::North) };
+				assert_eq!(
+					state.board.get_cell(&Coordinates::new(row, col)),
+					expected,
+					"Unexpected cell ({row}, {col}) after a {power_level:?} explosion at {epicenter:?}:\n{}",
+					state.board
+				);
+			}
+		}
+	}
+}
+
+#[test]
+fn bombs_explode_within_the_corners_of_the_board() {
+	for config in [GameConfig::CLASSIC, GameConfig::QUICK] {
+		let last_row = config.board_height - 1;
+		let last_col = config.board_width - 1;
+		for epicenter in [
+			Coordinates::new(0, 0),
+			Coordinates::new(0, last_col),
+			Coordinates::new(last_row, 0),
+			Coordinates::new(last_row, last_col),
+		] {
+			assert_explosion_is_clipped_to_the_board(config, epicenter);
+		}
+	}
+}
+
+#[test]
+fn bombs_explode_within_the_edges_of_the_board() {
+	for config in [GameConfig::CLASSIC, GameConfig::QUICK] {
+		let last_row = config.board_height - 1;
+		let last_col = config.board_width - 1;
+		for position in 1..last_col {
+			assert_explosion_is_clipped_to_the_board(config, Coordinates::new(0, position));
+			assert_explosion_is_clipped_to_the_board(config, Coordinates::new(last_row, position));
+		}
+		for position in 1..last_row {
+			assert_explosion_is_clipped_to_the_board(config, Coordinates::new(position, 0));
+			assert_explosion_is_clipped_to_the_board(config, Coordinates::new(position, last_col));
+		}
+	}
+}
+
+#[test]
+fn a_bomb_detonated_in_a_corner_does_not_panic() {
+	let mut state = Game::new_game(ALICE, BOB, Some(INITIAL_SEED));
+	let corner = Coordinates::new(BOARD_HEIGHT - 1, BOARD_WIDTH - 1);
+	state.board.update_cell(&corner, Cell::Empty);
+	let salt = HashSalt::default();
+
+	state = Game::place_bomb(state, ALICE, corner, salt).unwrap();
+	state = Game::place_bomb(state, BOB, Coordinates::new(0, 0), salt).unwrap();
+	state.board.update_cell(&corner, Cell::Stone(1, Side::North));
+	state = Game::detonate_bomb(state, ALICE, corner, salt, PowerLevel::Three).unwrap();
+	assert_eq!(state.board.get_cell(&corner), Cell::Empty);
+}
+
+#[test]
+fn stones_settle_toward_the_side_they_were_dropped_from() {
+	let mut board = Board::new();
+	board.update_cell(&Coordinates::new(1, 8), Cell::Stone(0, Side::North));
+	board.update_cell(&Coordinates::new(9, 8), Cell::Stone(1, Side::North));
+	board.update_cell(&Coordinates::new(2, 5), Cell::Stone(0, Side::East));
+	board.update_cell(&Coordinates::new(3, 6), Cell::Stone(1, Side::West));
+	board.update_cell(&Coordinates::new(3, 2), Cell::Block);
+	board.update_cell(&Coordinates::new(5, 7), Cell::Stone(0, Side::South));
+
+	board.settle_stones();
+
+	assert_eq!(board.get_cell(&Coordinates::new(0, 8)), Cell::Stone(0, Side::North), "\n{}", board);
+	assert_eq!(board.get_cell(&Coordinates::new(1, 8)), Cell::Stone(1, Side::North));
+	assert_eq!(board.get_cell(&Coordinates::new(2, 9)), Cell::Stone(0, Side::East));
+	assert_eq!(board.get_cell(&Coordinates::new(3, 3)), Cell::Stone(1, Side::West));
+	assert_eq!(board.get_cell(&Coordinates::new(3, 2)), Cell::Block);
+	assert_eq!(board.get_cell(&Coordinates::new(9, 7)), Cell::Stone(0, Side::South));
+	for coordinates in [(9, 8), (2, 5), (3, 6), (5, 7)] {
+		let coordinates = Coordinates::new(coordinates.0, coordinates.1);
+		assert_eq!(board.get_cell(&coordinates), Cell::Empty, "\n{}", board);
+	}
+}
+
+#[test]
+fn stones_settling_after_an_explosion_can_win_the_game() {
+	for config in [GameConfig::CLASSIC, GameConfig::GRAVITY] {
+		let salt = HashSalt::default();
+		let bomb = Coordinates::new(9, 0);
+		let mut state =
+			Game::new_game_with_config(BOARD_ID, vec![ALICE, BOB], Some(INITIAL_SEED), config)
+				.unwrap();
+		state.config.squares_to_win = 1;
+		state.board = Board::new();
+		state = Game::place_bomb(state, ALICE, bomb, salt).unwrap();
+		state = Game::place_bomb(state, BOB, Coordinates::new(0, 5), salt).unwrap();
+
+		let (alice, bob) = (state.player_index(&ALICE), state.player_index(&BOB));
+		for (row, col, player_index) in
+			[(7, 0, alice), (8, 0, alice), (9, 0, bob), (8, 1, alice), (9, 1, alice)]
+		{
+			state
+				.board
+				.update_cell(&Coordinates::new(row, col), Cell::Stone(player_index, Side::South));
+		}
+		assert!(Game::check_winner_player(state.clone()).winner.is_none());
+
+		state = Game::detonate_bomb(state, ALICE, bomb, salt, PowerLevel::One).unwrap();
+		if config.gravity_after_explosions {
+			assert_eq!(state.board.get_cell(&bomb), Cell::Stone(alice, Side::South));
+			assert_eq!(state.board.get_cell(&Coordinates::new(7, 0)), Cell::Empty);
+			assert_eq!(state.winner, Some(ALICE), "\n{}", state);
+		} else {
+			assert_eq!(state.board.get_cell(&bomb), Cell::Empty);
+			assert_eq!(state.winner, None, "\n{}", state);
+		}
+	}
+}
+
+#[test]
+fn a_hidden_bomb_hits_the_opponent_stone_landing_on_it() {
+	let salt = HashSalt::repeat_byte(3);
+	let bomb = Coordinates::new(BOARD_HEIGHT - 1, 0);
+	let mut state = Game::new_game_with_config(
+		BOARD_ID,
+		vec![ALICE, BOB],
+		Some(INITIAL_SEED),
+		GameConfig::HIDDEN_BOMBS,
+	)
+	.unwrap();
+	state.board = Board::new();
+
+	let commitment = Game::hash_coordinates(bomb, salt, &ALICE, BOARD_ID);
+	state = Game::hide_bomb(state, ALICE, commitment).unwrap();
+	assert_eq!(state.bombs_placed[state.player_index(&ALICE) as usize].to_vec(), vec![commitment]);
+
+	state = Game::drop_stone(state, BOB, Side::North, 0).unwrap();
+	assert_eq!(state.landed_stone, Some(bomb));
+	assert!(Game::legal_moves(&state, &ALICE).contains(&LegalMove::ClaimHit));
+	assert_eq!(
+		Game::claim_hit(state.clone(), BOB, bomb, salt),
+		Err(GameError::NoHitToClaim),
+		"Bob cannot claim a hit on his own stone"
+	);
+	assert_eq!(
+		Game::claim_hit(state.clone(), ALICE, bomb, HashSalt::repeat_byte(4)),
+		Err(GameError::InvalidBombCoordinates)
+	);
+
+	state = Game::claim_hit(state, ALICE, bomb, salt).unwrap();
+	assert_eq!(state.board.get_cell(&bomb), Cell::Empty);
+	assert!(state.bombs_placed.iter().all(|bombs| bombs.is_empty()));
+	assert_eq!(state.next_player, ALICE, "Alice should still have the turn");
+	assert_eq!(Game::claim_hit(state.clone(), ALICE, bomb, salt), Err(GameError::NoHitToClaim));
+	assert!(Game::drop_stone(state, ALICE, Side::North, 1).is_ok());
+}
+
+#[test]
+fn bombs_can_only_be_hidden_in_games_of_two_players() {
+	assert!(GameConfig::HIDDEN_BOMBS.is_valid());
+	assert!(GameConfig { players: 3, ..GameConfig::CLASSIC }.is_valid());
+	assert!(
+		!GameConfig { players: 3, ..GameConfig::HIDDEN_BOMBS }.is_valid(),
+		"Charlie could play on before Alice claims the hit on the stone of Bob"
+	);
+	assert!(!GameConfig { players: 4, ..GameConfig::HIDDEN_BOMBS }.is_valid());
+	assert!(!GameConfig { players: 4, teams: true, ..GameConfig::HIDDEN_BOMBS }.is_valid());
+}
+
+#[test]
+fn hits_on_stones_of_the_bomb_owner_cannot_be_claimed() {
+	let salt = HashSalt::repeat_byte(3);
+	let bomb = Coordinates::new(BOARD_HEIGHT - 1, 0);
+	let other_bomb = Coordinates::new(0, 0);
+
+	let mut state = Game::new_game_with_config(
+		BOARD_ID,
+		vec![ALICE, BOB],
+		Some(INITIAL_SEED),
+		GameConfig::HIDDEN_BOMBS,
+	)
+	.unwrap();
+	state.board = Board::new();
+	state = Game::place_bomb(state, ALICE, bomb, salt).unwrap();
+	state = Game::place_bomb(state, BOB, other_bomb, salt).unwrap();
+	state = Game::drop_stone(state, ALICE, Side::North, 0).unwrap();
+	assert_eq!(state.landed_stone, Some(bomb));
+	assert!(!Game::legal_moves(&state, &ALICE).contains(&LegalMove::ClaimHit));
+	assert_eq!(Game::claim_hit(state, ALICE, bomb, salt), Err(GameError::NoHitToClaim));
+}
+
+#[test]
+fn a_hit_can_only_be_claimed_right_after_the_stone_landed() {
+	let salt = HashSalt::repeat_byte(3);
+	let bomb = Coordinates::new(BOARD_HEIGHT - 1, 0);
+	let mut state = Game::new_game_with_config(
+		BOARD_ID,
+		vec![ALICE, BOB],
+		Some(INITIAL_SEED),
+		GameConfig::HIDDEN_BOMBS,
+	)
+	.unwrap();
+	state.board = Board::new();
+	state = Game::place_bomb(state, ALICE, bomb, salt).unwrap();
+	state = Game::drop_stone(state, BOB, Side::North, 0).unwrap();
+
+	state = Game::drop_stone(state, ALICE, Side::North, 5).unwrap();
+	state = Game::drop_stone(state, BOB, Side::North, 6).unwrap();
+	assert_eq!(state.landed_stone, Some(Coordinates::new(BOARD_HEIGHT - 1, 6)));
+	assert_eq!(
+		Game::claim_hit(state.clone(), ALICE, bomb, salt),
+		Err(GameError::InvalidBombCoordinates)
+	);
+	assert!(!Game::legal_moves(&state, &BOB).contains(&LegalMove::ClaimHit));
+
+	let mut classic = state.clone();
+	classic.config.hidden_bombs = false;
+	classic.landed_stone = Some(bomb);
+	assert_eq!(Game::claim_hit(classic, ALICE, bomb, salt), Err(GameError::NoHitToClaim));
+}
+
+#[test]
+fn a_game_is_created_for_as_many_players_as_its_rules_need() {
+	let three_players = GameConfig { players: 3, ..GameConfig::CLASSIC };
+	let state =
+		Game::new_game_with_config(BOARD_ID, vec![ALICE, BOB, CHARLIE], None, three_players)
+			.unwrap();
+	assert_eq!(state.players.to_vec(), vec![ALICE, BOB, CHARLIE]);
+	assert_eq!(state.bomb_energy.len(), 3);
+	assert_eq!(state.bombs_placed.len(), 3);
+	assert_eq!(state.next_player, ALICE);
+
+	for players in [vec![ALICE], vec![ALICE, BOB], vec![ALICE, BOB, CHARLIE, DAVE]] {
+		assert_eq!(
+			Game::new_game_with_config(BOARD_ID, players, None, three_players),
+			Err(GameError::InvalidPlayers)
+		);
+	}
+	assert!(!GameConfig { players: 1, ..GameConfig::CLASSIC }.is_valid());
+	assert!(!GameConfig { players: MAX_PLAYERS + 1, ..GameConfig::CLASSIC }.is_valid());
+}
+
+#[test]
+fn players_take_their_turns_round_robin_with_their_own_stones() {
+	let players = vec![ALICE, BOB, CHARLIE, DAVE];
+	let mut state = Game::new_game_with_config(
+		BOARD_ID,
+		players.clone(),
+		Some(INITIAL_SEED),
+		GameConfig::FREE_FOR_ALL,
+	)
+	.unwrap();
+	state.board = Board::new();
+	state.config.bombs_per_player = 0;
+
+	for (position, player) in players.iter().enumerate() {
+		assert_eq!(state.next_player, *player);
+		let not_their_turn = players[(position + 1) % players.len()];
+		assert_eq!(
+			Game::drop_stone(state.clone(), not_their_turn, Side::North, position as u8),
+			Err(GameError::NotPlayerTurn)
+		);
+		state = Game::drop_stone(state, *player, Side::North, position as u8).unwrap();
+		assert_eq!(
+			state.board.get_cell(&Coordinates::new(BOARD_HEIGHT - 1, position as u8)),
+			Cell::Stone(position as PlayerIndex, Side::North)
+		);
+	}
+	assert_eq!(state.next_player, ALICE);
+}
+
+#[test]
+fn players_are_ranked_by_their_squares_after_the_winner() {
+	let mut state = Game::new_game_with_config(
+		BOARD_ID,
+		vec![ALICE, BOB, CHARLIE, DAVE],
+		Some(INITIAL_SEED),
+		GameConfig::FREE_FOR_ALL,
+	)
+	.unwrap();
+	let o = Cell::Empty;
+	let a = Cell::Stone(0, Side::North);
+	let b = Cell::Stone(1, Side::North);
+	let c = Cell::Stone(2, Side::North);
+	let d = Cell::Stone(3, Side::North);
+	state.board.cells = [
+		[a, a, o, d, d, d, o, o, o, o],
+		[a, a, o, d, d, d, o, o, o, o],
+		[o, o, o, o, o, o, o, o, o, o],
+		[c, c, c, o, o, o, o, o, o, o],
+		[c, c, c, o, o, o, o, o, o, o],
+		[o, o, o, o, o, o, o, o, o, o],
+		[o, o, o, o, b, b, o, b, b, o],
+		[o, o, o, o, b, b, o, b, b, o],
+		[o, o, o, o, o, o, o, b, b, o],
+		[o, o, o, o, o, o, o, o, o, o],
+	];
+	assert_eq!(state.squares(), vec![1, 3, 2, 2]);
+
+	state = Game::check_winner_player(state);
+	assert_eq!(state.winner, Some(BOB));
+	assert_eq!(state.ranking(), vec![BOB, CHARLIE, DAVE, ALICE]);
+	assert_eq!(state.standings(), vec![vec![BOB], vec![CHARLIE, DAVE], vec![ALICE]]);
+
+	state.winner = Some(ALICE);
+	assert_eq!(state.ranking(), vec![ALICE, BOB, CHARLIE, DAVE]);
+}
+
+#[test]
+fn team_mates_share_their_squares_and_win_together() {
+	let mut state = Game::new_game_with_config(
+		BOARD_ID,
+		vec![ALICE, BOB, CHARLIE, DAVE],
+		Some(INITIAL_SEED),
+		GameConfig::TEAMS,
+	)
+	.unwrap();
+	assert_eq!(state.teams(), vec![0, 1, 0, 1]);
+	assert!(!GameConfig { players: 3, ..GameConfig::TEAMS }.is_valid());
+
+	let o = Cell::Empty;
+	let a = Cell::Stone(0, Side::North);
+	let b = Cell::Stone(1, Side::North);
+	let c = Cell::Stone(2, Side::North);
+	let d = Cell::Stone(3, Side::North);
+	state.board.cells = [
+		[o, o, o, o, o, o, o, o, o, o],
+		[o, o, o, o, o, o, o, o, o, o],
+		[o, o, o, o, o, o, o, o, o, o],
+		[o, o, o, o, o, o, o, o, o, o],
+		[o, o, o, o, o, o, b, d, o, o],
+		[o, o, o, o, o, o, d, b, o, o],
+		[o, o, o, o, o, o, o, o, o, o],
+		[o, o, o, o, o, o, o, o, o, o],
+		[a, c, a, c, o, o, o, o, o, o],
+		[c, a, c, a, o, o, o, o, o, o],
+	];
+	assert_eq!(state.squares(), vec![3, 1, 3, 1]);
+
+	state = Game::check_winner_player(state);
+	assert_eq!(state.winner, Some(ALICE));
+	assert_eq!(state.winners(), vec![ALICE, CHARLIE]);
+	assert_eq!(state.ranking(), vec![ALICE, CHARLIE, BOB, DAVE]);
+	assert_eq!(state.standings(), vec![vec![ALICE, CHARLIE], vec![BOB, DAVE]]);
+}
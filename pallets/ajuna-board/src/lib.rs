@@ -17,13 +17,22 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::{Codec, Decode, Encode};
-use frame_support::pallet_prelude::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, Imbalance, Randomness, ReservableCurrency},
+};
 use frame_system::{ensure_signed, pallet_prelude::*};
-use pallet_ajuna_matchmaker::{Matchmaker, DEFAULT_BRACKET};
-use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumberProvider, Saturating};
+use pallet_ajuna_matchmaker::{Bracket, Matchmaker};
+use sp_core::H256;
+use sp_io::hashing::blake2_256;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, BlockNumberProvider, Saturating, TrailingZeroInput, Zero},
+	Permill,
+};
 use sp_std::vec::Vec;
 
 pub use pallet::*;
+pub use weights::WeightInfo;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
@@ -35,8 +44,12 @@ mod mock;
 mod tests;
 
 pub mod dot4gravity;
+pub mod migration;
+pub mod runtime_api;
 pub mod types;
+pub mod weights;
 
+use dot4gravity::TeamIndex;
 use types::*;
 
 #[frame_support::pallet]
@@ -50,21 +63,70 @@ pub mod pallet {
 		/// Board id
 		type BoardId: Copy + Default + AtLeast32BitUnsigned + Parameter + MaxEncodedLen;
 		/// A Turn for the game
-		type PlayersTurn: Member + Parameter + From<Turn>;
+		type PlayersTurn: Member + Parameter + MaxEncodedLen + From<Turn>;
 		/// The state of the board
 		type GameState: Codec + TypeInfo + MaxEncodedLen + Clone;
+		/// The reason a turn was rejected by the game
+		type TurnError: Into<Error<Self>>;
+		/// Rules a game is played with
+		type GameConfig: Parameter + MaxEncodedLen;
 		/// A turn based game
 		type Game: TurnBasedGame<
 			Player = Self::AccountId,
 			Turn = Self::PlayersTurn,
 			State = Self::GameState,
+			TurnError = Self::TurnError,
+			Config = Self::GameConfig,
+			BoardId = Self::BoardId,
 		>;
-		/// Number of players required for a game.
+		/// Rules of the games that players can queue for, indexed by their game mode.
+		#[pallet::constant]
+		type GameModes: Get<Vec<Self::GameConfig>>;
+		/// Most players a game can be played by.
 		#[pallet::constant]
 		type Players: Get<u32>;
+		/// Number of blocks a player has to play their turn, after which the other players can
+		/// claim the game as timed out.
+		#[pallet::constant]
+		type IdleBoardTimeout: Get<BlockNumberFor<Self>>;
+		/// Currency in which the stakes of the games are held.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Stakes that players can put on a game. Only players with the same stake are matched.
+		#[pallet::constant]
+		type StakeTiers: Get<Vec<BalanceOf<Self>>>;
+		/// Share of the pot of a staked game that is sent to the treasury.
+		#[pallet::constant]
+		type Rake: Get<Permill>;
+		/// Account receiving the rake of staked games.
+		#[pallet::constant]
+		type Treasury: Get<Self::AccountId>;
+		/// Source of randomness for the seeds of new games. Its output should not be known before
+		/// the call creating the game is included, or players can time their queueing to pick
+		/// the board they get.
+		type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
+		/// Whether matched players seed their board by committing to and revealing secrets,
+		/// instead of relying on on-chain randomness.
+		#[pallet::constant]
+		type CommitReveal: Get<bool>;
+		/// Number of blocks a finished board is kept for, after which only its result remains.
+		#[pallet::constant]
+		type BoardRetention: Get<BlockNumberFor<Self>>;
+		/// Number of recent games whose results are kept for each account.
+		#[pallet::constant]
+		type MaxRecentResults: Get<u32>;
+		/// Number of turns kept in the move log of a board, which should cover the longest game.
+		/// A game whose move log is full ends in a draw.
+		#[pallet::constant]
+		type MaxMoves: Get<u32>;
+		/// Weights of the calls of the pallet
+		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::event]
@@ -75,13 +137,60 @@ pub mod pallet {
 			board_id: T::BoardId,
 			players: Vec<T::AccountId>,
 		},
-		/// Game has finished with the winner
+		/// Game has finished with the winner and its team mates
 		GameFinished {
 			board_id: T::BoardId,
-			winner: T::AccountId,
+			winners: Vec<T::AccountId>,
 		},
 
 		NoMatchFound,
+		/// The pot of a staked game has been paid out to the winners, after the rake
+		PotPaidOut {
+			board_id: T::BoardId,
+			winners: Vec<T::AccountId>,
+			pot: BalanceOf<T>,
+			rake: BalanceOf<T>,
+		},
+		/// Player has played a turn
+		TurnPlayed {
+			board_id: T::BoardId,
+			player: T::AccountId,
+			turn: T::PlayersTurn,
+		},
+		/// Player has committed to a secret seeding the board
+		SecretCommitted {
+			board_id: T::BoardId,
+			player: T::AccountId,
+		},
+		/// Player has revealed the secret seeding the board
+		SecretRevealed {
+			board_id: T::BoardId,
+			player: T::AccountId,
+		},
+		/// All secrets have been revealed and the board is built
+		GameStarted {
+			board_id: T::BoardId,
+		},
+		/// Game has finished without a winner
+		GameDrawn {
+			board_id: T::BoardId,
+			players: Vec<T::AccountId>,
+		},
+		/// Game has been ended because a player did not play their turn in time
+		GameTimedOut {
+			board_id: T::BoardId,
+			player: T::AccountId,
+		},
+		/// Game has been forfeited by a player, leaving the win to the remaining players
+		GameForfeited {
+			board_id: T::BoardId,
+			player: T::AccountId,
+			winners: Vec<T::AccountId>,
+		},
+		/// Game has been cancelled before it started, with the stakes returned to its players
+		GameCancelled {
+			board_id: T::BoardId,
+		},
 	}
 
 	#[pallet::error]
@@ -94,6 +203,27 @@ pub mod pallet {
 		AlreadyQueued,
 		UnknownBoard,
 		BoardInUse,
+		TurnNotTimedOut,
+		CannotClaimOwnTurn,
+		NotQueued,
+		NoMoreBombsAvailable,
+		InsufficientBombEnergy,
+		InvalidBombCoordinates,
+		InvalidStonePosition,
+		NotPlayerTurn,
+		NoPreviousPosition,
+		GameAlreadyFinished,
+		InvalidStake,
+		InvalidPhase,
+		AlreadyCommitted,
+		AlreadyRevealed,
+		InvalidReveal,
+		MoveLogFull,
+		InvalidGameMode,
+		NoHitToClaim,
+		InParty,
+		NotPartyLeader,
+		PartyTooLarge,
 	}
 
 	#[pallet::storage]
@@ -106,44 +236,125 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type PlayerBoards<T: Config> = StorageMap<_, Identity, T::AccountId, T::BoardId>;
 
-	/// Random seed
+	/// Brackets the queued players wait in for a match
+	#[pallet::storage]
+	pub type QueuedBrackets<T: Config> = StorageMap<_, Identity, T::AccountId, Bracket>;
+
+	/// Stakes held for the players queued or playing in a staked game
+	#[pallet::storage]
+	pub type PlayerStakes<T: Config> = StorageMap<_, Identity, T::AccountId, BalanceOf<T>>;
+
+	/// Commitments of the players to the secrets seeding their board
+	#[pallet::storage]
+	pub type Commitments<T: Config> =
+		StorageDoubleMap<_, Identity, T::BoardId, Identity, T::AccountId, H256>;
+
+	/// Secrets revealed by the players to seed their board
+	#[pallet::storage]
+	pub type Reveals<T: Config> =
+		StorageDoubleMap<_, Identity, T::BoardId, Identity, T::AccountId, H256>;
+
+	/// Turns played on each board in order, indexed by the number of turns played before them
+	#[pallet::storage]
+	pub type Moves<T: Config> = StorageDoubleMap<_, Identity, T::BoardId, Identity, u32, MoveOf<T>>;
+
+	/// Results of the finished games
+	#[pallet::storage]
+	pub type GameResults<T: Config> = StorageMap<_, Identity, T::BoardId, GameResultOf<T>>;
+
+	/// Statistics of the games played by each account
+	#[pallet::storage]
+	pub type Stats<T: Config> = StorageMap<_, Identity, T::AccountId, PlayerStats, ValueQuery>;
+
+	/// Boards of the latest games finished by each account, the most recent last
+	#[pallet::storage]
+	pub type RecentResults<T: Config> =
+		StorageMap<_, Identity, T::AccountId, RecentResultsOf<T>, ValueQuery>;
+
+	/// Finished boards in the order they are pruned, with the block from which they can be
+	#[pallet::storage]
+	pub type PruneQueue<T: Config> = StorageMap<_, Identity, u32, (T::BoardId, BlockNumberFor<T>)>;
+
+	/// Start and end indices of the boards waiting in the prune queue
+	#[pallet::storage]
+	pub type PruneQueueRange<T> = StorageValue<_, (u32, u32), ValueQuery>;
+
+	/// Seed used for the next game instead of on-chain randomness, to create deterministic boards.
+	/// Only tests and benchmarks read it, so it cannot steer the boards of a live chain.
 	#[pallet::storage]
 	pub type GameSeed<T> = StorageValue<_, u32>;
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::prune_boards(now, remaining_weight)
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::call_index(0)]
-		#[pallet::weight({12_345})]
-		pub fn queue(origin: OriginFor<T>) -> DispatchResult {
+		#[pallet::weight(T::WeightInfo::queue())]
+		pub fn queue(
+			origin: OriginFor<T>,
+			stake: Option<BalanceOf<T>>,
+			mode: GameMode,
+		) -> DispatchResult {
 			let player = ensure_signed(origin)?;
-			ensure!(T::Matchmaker::enqueue(player, DEFAULT_BRACKET), Error::<T>::AlreadyQueued);
-			if let Some(players) = T::Matchmaker::try_match(DEFAULT_BRACKET, T::Players::get()) {
-				Self::create_game(players)?;
+			ensure!(!PlayerBoards::<T>::contains_key(&player), Error::<T>::AlreadyInGame);
+			ensure!(T::Matchmaker::party(&player).is_empty(), Error::<T>::InParty);
+			let bracket = Self::bracket_for(&player, stake, mode)?;
+			ensure!(T::Matchmaker::enqueue(player.clone(), bracket), Error::<T>::AlreadyQueued);
+			QueuedBrackets::<T>::insert(&player, bracket);
+			if let Some(stake) = stake {
+				T::Currency::reserve(&player, stake)?;
+				PlayerStakes::<T>::insert(&player, stake);
+			}
+			let players = T::Game::players(&Self::game_config(mode)?);
+			if let Some(players) = T::Matchmaker::try_match(bracket, players) {
+				Self::create_game(players, mode)?;
 			};
 			Ok(())
 		}
 
 		#[pallet::call_index(1)]
-		#[pallet::weight({12_345})]
+		#[pallet::weight(T::WeightInfo::play().max(T::WeightInfo::play_turn_until_finished()))]
 		pub fn play(origin: OriginFor<T>, turn: T::PlayersTurn) -> DispatchResult {
 			let player = ensure_signed(origin)?;
 			let board_id = PlayerBoards::<T>::get(&player).ok_or(Error::<T>::NotPlaying)?;
 
 			let mut board_game = BoardGames::<T>::get(board_id).ok_or(Error::<T>::UnknownBoard)?;
-			let new_state = T::Game::play_turn(player, board_game.state, turn)
-				.ok_or(Error::<T>::InvalidTurn)?;
-
-			if let Finished::Winner::<T::AccountId>(winner) = T::Game::is_finished(&new_state) {
-				Self::finish_game(board_id, winner)?;
-			} else {
-				board_game.state = new_state;
-				BoardGames::<T>::insert(board_id, board_game);
+			ensure!(board_game.phase == GamePhase::Playing, Error::<T>::InvalidPhase);
+			ensure!(board_game.turns < T::MaxMoves::get(), Error::<T>::MoveLogFull);
+			// Hits can be claimed out of turn, which must not give the next player more time
+			let in_turn = T::Game::get_next_player(&board_game.state) == player;
+			board_game.state = T::Game::play_turn(player.clone(), board_game.state, turn.clone())
+				.map_err(Into::<Error<T>>::into)?;
+			let now = frame_system::Pallet::<T>::current_block_number();
+			let record = Move { player: player.clone(), turn: turn.clone(), block: now };
+			Moves::<T>::insert(board_id, board_game.turns, record);
+			board_game.turns.saturating_inc();
+			if in_turn {
+				board_game.last_turn = now;
+			}
+			// A game whose move log is full cannot go on, so it ends in a draw
+			let finished = match T::Game::is_finished(&board_game.state) {
+				Finished::No if board_game.turns >= T::MaxMoves::get() => Finished::Draw,
+				finished => finished,
+			};
+			BoardGames::<T>::insert(board_id, &board_game);
+			Self::deposit_event(Event::TurnPlayed { board_id, player, turn });
+
+			match finished {
+				Finished::No => Ok(()),
+				Finished::Winner(winner) => Self::finish_game(board_id, &board_game, &[winner]),
+				Finished::Ranked(ranking) => Self::rank_game(board_id, &board_game, &ranking),
+				Finished::Draw => Self::draw_game(board_id),
 			}
-			Ok(())
 		}
 
 		#[pallet::call_index(2)]
-		#[pallet::weight({12_345})]
+		#[pallet::weight(T::WeightInfo::clear_board())]
 		pub fn clear_board(origin: OriginFor<T>, board_id: T::BoardId) -> DispatchResult {
 			ensure_root(origin)?;
 
@@ -158,6 +369,7 @@ pub mod pallet {
 					);
 
 					*maybe_board_game = None;
+					let _ = Moves::<T>::clear_prefix(board_id, T::MaxMoves::get(), None);
 
 					Ok(())
 				} else {
@@ -166,45 +378,583 @@ pub mod pallet {
 			})
 			.map_err(|err| err.into())
 		}
+
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::claim_timeout())]
+		pub fn claim_timeout(origin: OriginFor<T>) -> DispatchResult {
+			let player = ensure_signed(origin)?;
+			let board_id = PlayerBoards::<T>::get(&player).ok_or(Error::<T>::NotPlaying)?;
+
+			let board_game = BoardGames::<T>::get(board_id).ok_or(Error::<T>::UnknownBoard)?;
+			let idle_players = Self::idle_players(board_id, &board_game);
+			let idle_teams = Self::teams_of(&board_game, &idle_players);
+			// A board nobody seeded in time is left to nobody, so any of its players cancels it
+			let cancelled = board_game.phase != GamePhase::Playing &&
+				idle_teams.len() == board_game.players.len();
+			ensure!(cancelled || !idle_teams.contains(&player), Error::<T>::CannotClaimOwnTurn);
+
+			let now = frame_system::Pallet::<T>::current_block_number();
+			let deadline = board_game.last_turn.saturating_add(T::IdleBoardTimeout::get());
+			ensure!(now > deadline, Error::<T>::TurnNotTimedOut);
+
+			if cancelled {
+				return Self::cancel_game(board_id, board_game)
+			}
+
+			let winners = board_game
+				.players
+				.iter()
+				.filter(|other| !idle_teams.contains(other))
+				.cloned()
+				.collect::<Vec<_>>();
+			for idle_player in idle_players {
+				Stats::<T>::mutate(&idle_player, |stats| stats.forfeits.saturating_inc());
+				Self::deposit_event(Event::GameTimedOut { board_id, player: idle_player });
+			}
+			Self::clear_commitments(board_id);
+			Self::award_game(board_id, board_game, &winners)
+		}
+
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::forfeit())]
+		pub fn forfeit(origin: OriginFor<T>) -> DispatchResult {
+			let player = ensure_signed(origin)?;
+			let board_id = PlayerBoards::<T>::get(&player).ok_or(Error::<T>::NotPlaying)?;
+
+			let board_game = BoardGames::<T>::get(board_id).ok_or(Error::<T>::UnknownBoard)?;
+			let team = Self::teams_of(&board_game, &[player.clone()]);
+			let winners = board_game
+				.players
+				.iter()
+				.filter(|other| !team.contains(other))
+				.cloned()
+				.collect::<Vec<_>>();
+
+			Stats::<T>::mutate(&player, |stats| stats.forfeits.saturating_inc());
+			Self::deposit_event(Event::GameForfeited {
+				board_id,
+				player,
+				winners: winners.clone(),
+			});
+			Self::clear_commitments(board_id);
+			Self::award_game(board_id, board_game, &winners)
+		}
+
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::unqueue())]
+		pub fn unqueue(origin: OriginFor<T>) -> DispatchResult {
+			let player = ensure_signed(origin)?;
+			let bracket = QueuedBrackets::<T>::take(&player).ok_or(Error::<T>::NotQueued)?;
+			ensure!(T::Matchmaker::dequeue(player.clone(), bracket), Error::<T>::NotQueued);
+			T::Matchmaker::party(&player).iter().for_each(QueuedBrackets::<T>::remove);
+			if let Some(stake) = PlayerStakes::<T>::take(&player) {
+				T::Currency::unreserve(&player, stake);
+			}
+			Ok(())
+		}
+
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::commit())]
+		pub fn commit(origin: OriginFor<T>, commitment: H256) -> DispatchResult {
+			let player = ensure_signed(origin)?;
+			let board_id = PlayerBoards::<T>::get(&player).ok_or(Error::<T>::NotPlaying)?;
+
+			let mut board_game = BoardGames::<T>::get(board_id).ok_or(Error::<T>::UnknownBoard)?;
+			ensure!(board_game.phase == GamePhase::AwaitingCommitments, Error::<T>::InvalidPhase);
+			ensure!(
+				!Commitments::<T>::contains_key(board_id, &player),
+				Error::<T>::AlreadyCommitted
+			);
+
+			Commitments::<T>::insert(board_id, &player, commitment);
+			Self::deposit_event(Event::SecretCommitted { board_id, player });
+
+			if board_game
+				.players
+				.iter()
+				.all(|player| Commitments::<T>::contains_key(board_id, player))
+			{
+				board_game.phase = GamePhase::AwaitingReveals;
+				board_game.last_turn = frame_system::Pallet::<T>::current_block_number();
+				BoardGames::<T>::insert(board_id, board_game);
+			}
+			Ok(())
+		}
+
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::reveal())]
+		pub fn reveal(origin: OriginFor<T>, secret: H256) -> DispatchResult {
+			let player = ensure_signed(origin)?;
+			let board_id = PlayerBoards::<T>::get(&player).ok_or(Error::<T>::NotPlaying)?;
+
+			let mut board_game = BoardGames::<T>::get(board_id).ok_or(Error::<T>::UnknownBoard)?;
+			ensure!(board_game.phase == GamePhase::AwaitingReveals, Error::<T>::InvalidPhase);
+			ensure!(!Reveals::<T>::contains_key(board_id, &player), Error::<T>::AlreadyRevealed);
+			ensure!(
+				Commitments::<T>::get(board_id, &player) ==
+					Some(Self::commitment_of(board_id, &secret, &player)),
+				Error::<T>::InvalidReveal
+			);
+
+			Reveals::<T>::insert(board_id, &player, secret);
+			Self::deposit_event(Event::SecretRevealed { board_id, player });
+
+			if board_game
+				.players
+				.iter()
+				.all(|player| Reveals::<T>::contains_key(board_id, player))
+			{
+				let secret = Reveals::<T>::iter_prefix_values(board_id)
+					.fold(H256::zero(), |seed, secret| seed ^ secret);
+				let seed = u32::decode(&mut TrailingZeroInput::new(secret.as_bytes()))
+					.expect("input is padded with zeroes; qed");
+
+				let config = Self::game_config(board_game.mode)?;
+				board_game.state = T::Game::init(board_id, &board_game.players, Some(seed), config)
+					.ok_or(Error::<T>::InvalidGameState)?;
+				board_game.phase = GamePhase::Playing;
+				board_game.last_turn = frame_system::Pallet::<T>::current_block_number();
+				BoardGames::<T>::insert(board_id, board_game);
+				Self::clear_commitments(board_id);
+				Self::deposit_event(Event::GameStarted { board_id });
+			}
+			Ok(())
+		}
+
+		/// Queue the party led by the origin to be matched as a whole, in the bracket of its
+		/// leader. Party games are played without stakes.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::queue_party())]
+		pub fn queue_party(origin: OriginFor<T>, mode: GameMode) -> DispatchResult {
+			let leader = ensure_signed(origin)?;
+			let party = T::Matchmaker::party(&leader);
+			ensure!(party.first() == Some(&leader), Error::<T>::NotPartyLeader);
+			for member in &party {
+				ensure!(!PlayerBoards::<T>::contains_key(member), Error::<T>::AlreadyInGame);
+			}
+			let players = T::Game::players(&Self::game_config(mode)?);
+			ensure!(party.len() as u32 <= players, Error::<T>::PartyTooLarge);
+
+			let bracket = Self::bracket_for(&leader, None, mode)?;
+			ensure!(T::Matchmaker::enqueue_party(leader, bracket), Error::<T>::AlreadyQueued);
+			party.iter().for_each(|member| QueuedBrackets::<T>::insert(member, bracket));
+			if let Some(players) = T::Matchmaker::try_match(bracket, players) {
+				Self::create_game(players, mode)?;
+			};
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
-	fn create_game(players: Vec<PlayerOf<T>>) -> DispatchResult {
+	fn create_game(players: Vec<PlayerOf<T>>, mode: GameMode) -> DispatchResult {
 		for player in &players {
 			ensure!(PlayerBoards::<T>::get(player).is_none(), Error::<T>::AlreadyInGame);
 		}
 
 		let board_id = NextBoardId::<T>::get();
-		let seed = GameSeed::<T>::get();
-		let state = T::Game::init(&players, seed).ok_or(Error::<T>::InvalidGameState)?;
-		Self::seed_for_next(&state);
+		let seed = Self::seed_for(board_id);
+		let config = Self::game_config(mode)?;
+		let state = T::Game::init(board_id, &players, Some(seed), config.clone())
+			.ok_or(Error::<T>::InvalidGameState)?;
+		let seated = Self::seat_parties(&players, &T::Game::teams(&state));
+		let (players, state) = if seated == players {
+			(players, state)
+		} else {
+			let state = T::Game::init(board_id, &seated, Some(seed), config)
+				.ok_or(Error::<T>::InvalidGameState)?;
+			(seated, state)
+		};
 
 		let bounded_players = players.clone().try_into().map_err(|_| Error::<T>::InvalidPlayers)?;
+		let teams = T::Game::teams(&state).try_into().map_err(|_| Error::<T>::InvalidPlayers)?;
 		let now = frame_system::Pallet::<T>::current_block_number();
-		let board_game = BoardGameOf::<T>::new(board_id, bounded_players, state, now);
-
-		players.iter().for_each(|player| PlayerBoards::<T>::insert(player, board_id));
+		// With commit-reveal the board is only built once the secrets are revealed
+		let phase = if T::CommitReveal::get() {
+			GamePhase::AwaitingCommitments
+		} else {
+			GamePhase::Playing
+		};
+		let board_game =
+			BoardGameOf::<T>::new(board_id, bounded_players, teams, state, now, phase, mode);
+
+		players.iter().for_each(|player| {
+			QueuedBrackets::<T>::remove(player);
+			PlayerBoards::<T>::insert(player, board_id);
+		});
 		BoardGames::<T>::insert(board_id, board_game);
 		NextBoardId::<T>::mutate(|board_id| board_id.saturating_inc());
 		Self::deposit_event(Event::GameCreated { board_id, players });
 		Ok(())
 	}
 
-	fn seed_for_next(game_state: &T::GameState) {
-		match T::Game::seed(game_state) {
-			Some(seed) => GameSeed::<T>::put(seed),
-			None => GameSeed::<T>::kill(),
+	/// Seats the members of each party in the same team, when it has room for all of them. The
+	/// other players take the remaining seats in the order they were matched.
+	fn seat_parties(players: &[T::AccountId], teams: &[TeamIndex]) -> Vec<T::AccountId> {
+		let mut seats = players.iter().map(|_| None).collect::<Vec<Option<T::AccountId>>>();
+		for player in players {
+			let party = T::Matchmaker::party(player);
+			if party.len() < 2 || party.first() != Some(player) {
+				continue
+			}
+			let free_seats = |seats: &[Option<T::AccountId>], team: TeamIndex| {
+				seats
+					.iter()
+					.zip(teams)
+					.filter(|(seat, seat_team)| seat.is_none() && **seat_team == team)
+					.count()
+			};
+			if let Some(team) =
+				teams.iter().copied().find(|team| free_seats(&seats, *team) >= party.len())
+			{
+				seats
+					.iter_mut()
+					.zip(teams)
+					.filter(|(seat, seat_team)| seat.is_none() && **seat_team == team)
+					.zip(party)
+					.for_each(|((seat, _), member)| *seat = Some(member));
+			}
 		}
+
+		let seated = seats.iter().flatten().cloned().collect::<Vec<_>>();
+		let mut others = players.iter().filter(|player| !seated.contains(player)).cloned();
+		seats.into_iter().filter_map(|seat| seat.or_else(|| others.next())).collect()
 	}
 
-	fn finish_game(board_id: T::BoardId, winner: PlayerOf<T>) -> DispatchResult {
-		BoardGames::<T>::get(board_id)
-			.ok_or(Error::<T>::UnknownBoard)?
+	fn game_config(mode: GameMode) -> Result<T::GameConfig, Error<T>> {
+		T::GameModes::get()
+			.get(mode as usize)
+			.cloned()
+			.ok_or(Error::<T>::InvalidGameMode)
+	}
+
+	/// Seed of a new board, from on-chain randomness unless tests or benchmarks fixed it
+	fn seed_for(board_id: T::BoardId) -> u32 {
+		#[cfg(any(test, feature = "runtime-benchmarks"))]
+		let fixed_seed = GameSeed::<T>::take();
+		#[cfg(not(any(test, feature = "runtime-benchmarks")))]
+		let fixed_seed = None;
+		fixed_seed.unwrap_or_else(|| Self::random_seed(board_id))
+	}
+
+	fn random_seed(board_id: T::BoardId) -> u32 {
+		let (random, _) = T::Randomness::random(&board_id.encode());
+		let hash = blake2_256(&(random, board_id).encode());
+		u32::decode(&mut TrailingZeroInput::new(&hash)).expect("input is padded with zeroes; qed")
+	}
+
+	/// Commitment to a secret for the seed of a board, binding it to the board and the player
+	pub fn commitment_of(board_id: T::BoardId, secret: &H256, player: &T::AccountId) -> H256 {
+		H256(blake2_256(&(board_id, secret, player).encode()))
+	}
+
+	/// Players holding up the game in its current phase
+	fn idle_players(board_id: T::BoardId, board_game: &BoardGameOf<T>) -> Vec<T::AccountId> {
+		let players = board_game.players.iter().cloned();
+		match board_game.phase {
+			GamePhase::AwaitingCommitments => players
+				.filter(|player| !Commitments::<T>::contains_key(board_id, player))
+				.collect(),
+			GamePhase::AwaitingReveals =>
+				players.filter(|player| !Reveals::<T>::contains_key(board_id, player)).collect(),
+			GamePhase::Playing => sp_std::vec![T::Game::get_next_player(&board_game.state)],
+			GamePhase::Finished => Vec::new(),
+		}
+	}
+
+	/// Players of the game in the same team as any of the given players, themselves included
+	fn teams_of(board_game: &BoardGameOf<T>, players: &[T::AccountId]) -> Vec<T::AccountId> {
+		let teams = board_game
+			.players
+			.iter()
+			.zip(board_game.teams.iter())
+			.filter(|(player, _)| players.contains(player))
+			.map(|(_, team)| *team)
+			.collect::<Vec<_>>();
+		board_game
 			.players
 			.iter()
-			.for_each(PlayerBoards::<T>::remove);
-		Self::deposit_event(Event::GameFinished { board_id, winner });
+			.zip(board_game.teams.iter())
+			.filter(|(_, team)| teams.contains(team))
+			.map(|(player, _)| player.clone())
+			.collect()
+	}
+
+	fn clear_commitments(board_id: T::BoardId) {
+		let _ = Commitments::<T>::clear_prefix(board_id, T::Players::get(), None);
+		let _ = Reveals::<T>::clear_prefix(board_id, T::Players::get(), None);
+	}
+
+	/// Ends the game in favour of the winners, after the other players forfeited it
+	fn award_game(
+		board_id: T::BoardId,
+		mut board_game: BoardGameOf<T>,
+		winners: &[T::AccountId],
+	) -> DispatchResult {
+		let winner = winners.first().ok_or(Error::<T>::InvalidGameState)?;
+		board_game.state = T::Game::abort(board_game.state, winner.clone());
+		BoardGames::<T>::insert(board_id, &board_game);
+		Self::finish_game(board_id, &board_game, winners)
+	}
+
+	/// Ends the game in favour of the winners and their teams
+	fn finish_game(
+		board_id: T::BoardId,
+		board_game: &BoardGameOf<T>,
+		winners: &[T::AccountId],
+	) -> DispatchResult {
+		let winners = Self::teams_of(board_game, winners);
+		let losers = board_game
+			.players
+			.iter()
+			.filter(|player| !winners.contains(player))
+			.cloned()
+			.collect::<Vec<_>>();
+		Self::rank_game(board_id, board_game, &[winners, losers])
+	}
+
+	/// Ends the game with its players ranked from first to last place. Their ratings follow the
+	/// whole ranking, while their statistics and the pot only tell the first place apart as the
+	/// winners of the game.
+	fn rank_game(
+		board_id: T::BoardId,
+		board_game: &BoardGameOf<T>,
+		ranking: &[Vec<T::AccountId>],
+	) -> DispatchResult {
+		let winners = match ranking.first() {
+			Some(winners) if !winners.is_empty() => winners.clone(),
+			_ => return Self::draw_game(board_id),
+		};
+		Self::release_players(&board_game.players);
+		T::Matchmaker::record_ranking(ranking);
+		Self::pay_out(board_id, &board_game.players, &winners);
+		Self::archive_game(board_id, board_game, &winners);
+		Self::deposit_event(Event::GameFinished { board_id, winners });
 		Ok(())
 	}
+
+	fn draw_game(board_id: T::BoardId) -> DispatchResult {
+		let board_game = BoardGames::<T>::get(board_id).ok_or(Error::<T>::UnknownBoard)?;
+		Self::release_players(&board_game.players);
+		T::Matchmaker::record_draw(&board_game.players);
+		Self::pay_out(board_id, &board_game.players, &board_game.players);
+		Self::archive_game(board_id, &board_game, &[]);
+		Self::deposit_event(Event::GameDrawn { board_id, players: board_game.players.into() });
+		Ok(())
+	}
+
+	/// Ends a game that never started, returning the stakes of its players
+	fn cancel_game(board_id: T::BoardId, board_game: BoardGameOf<T>) -> DispatchResult {
+		Self::release_players(&board_game.players);
+		Self::pay_out(board_id, &board_game.players, &[]);
+		Self::clear_commitments(board_id);
+		BoardGames::<T>::remove(board_id);
+		Self::deposit_event(Event::GameCancelled { board_id });
+		Ok(())
+	}
+
+	fn release_players(players: &[T::AccountId]) {
+		players.iter().for_each(PlayerBoards::<T>::remove);
+	}
+
+	/// Marks the game as finished, records its result in the statistics of its players and
+	/// queues its board to be pruned.
+	fn archive_game(board_id: T::BoardId, board_game: &BoardGameOf<T>, winners: &[T::AccountId]) {
+		let now = frame_system::Pallet::<T>::current_block_number();
+		BoardGames::<T>::mutate(board_id, |board_game| {
+			if let Some(board_game) = board_game {
+				board_game.phase = GamePhase::Finished;
+			}
+		});
+		let result = GameResultOf::<T> {
+			players: board_game.players.clone(),
+			winners: BoundedVec::truncate_from(winners.to_vec()),
+			started: board_game.started,
+			finished: now,
+			turns: board_game.turns,
+		};
+		GameResults::<T>::insert(board_id, result);
+
+		for player in board_game.players.iter() {
+			Stats::<T>::mutate(player, |stats| {
+				stats.played.saturating_inc();
+				if winners.is_empty() {
+					stats.draws.saturating_inc();
+					stats.win_streak = 0;
+				} else if winners.contains(player) {
+					stats.wins.saturating_inc();
+					stats.win_streak.saturating_inc();
+				} else {
+					stats.losses.saturating_inc();
+					stats.win_streak = 0;
+				}
+			});
+			RecentResults::<T>::mutate(player, |recent_results| {
+				// Nothing is kept, nor can be removed, when no results are kept at all
+				if !recent_results.is_empty() &&
+					recent_results.len() as u32 >= T::MaxRecentResults::get()
+				{
+					recent_results.remove(0);
+				}
+				let _ = recent_results.try_push(board_id);
+			});
+		}
+
+		PruneQueueRange::<T>::mutate(|(_, end)| {
+			PruneQueue::<T>::insert(*end, (board_id, now.saturating_add(T::BoardRetention::get())));
+			end.saturating_inc();
+		});
+	}
+
+	/// Removes the finished boards whose retention has passed, as far as the weight allows.
+	fn prune_boards(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+		let db_weight = T::DbWeight::get();
+		let (first, end) = PruneQueueRange::<T>::get();
+		let mut start = first;
+		let mut consumed = db_weight.reads(1);
+		// Besides the board, its whole move log may have to be removed
+		let prune_weight = db_weight.reads_writes(1, 2 + T::MaxMoves::get() as u64);
+
+		while start < end &&
+			remaining_weight.all_gte(
+				consumed.saturating_add(prune_weight).saturating_add(db_weight.writes(1)),
+			) {
+			consumed.saturating_accrue(db_weight.reads(1));
+			match PruneQueue::<T>::get(start) {
+				Some((_, prune_at)) if prune_at > now => break,
+				Some((board_id, _)) => {
+					BoardGames::<T>::remove(board_id);
+					PruneQueue::<T>::remove(start);
+					let moves = Moves::<T>::clear_prefix(board_id, T::MaxMoves::get(), None);
+					consumed.saturating_accrue(db_weight.writes(2 + moves.unique as u64));
+				},
+				None => {},
+			}
+			start.saturating_inc();
+		}
+
+		if start != first {
+			PruneQueueRange::<T>::put((start, end));
+			consumed.saturating_accrue(db_weight.writes(1));
+		}
+		consumed
+	}
+
+	/// Bracket in which the player is matched, given by its rating, the tier of its stake and the
+	/// game mode.
+	fn bracket_for(
+		player: &T::AccountId,
+		stake: Option<BalanceOf<T>>,
+		mode: GameMode,
+	) -> Result<Bracket, Error<T>> {
+		Self::game_config(mode)?;
+		let stake_tier = match stake {
+			Some(stake) => {
+				let tiers = T::StakeTiers::get();
+				let index = tiers.iter().position(|tier| *tier == stake);
+				index.ok_or(Error::<T>::InvalidStake)? as Bracket + 1
+			},
+			None => 0,
+		};
+		Ok(stake_tier << STAKE_TIER_SHIFT |
+			(mode as Bracket) << GAME_MODE_SHIFT |
+			T::Matchmaker::bracket_for(player))
+	}
+
+	/// Collects the stakes of the players into a pot and splits it evenly between the winners,
+	/// after sending the rake to the treasury. Stakes are returned when there is no winner.
+	fn pay_out(board_id: T::BoardId, players: &[T::AccountId], winners: &[T::AccountId]) {
+		let stakes = players
+			.iter()
+			.filter_map(|player| Some((player, PlayerStakes::<T>::take(player)?)))
+			.collect::<Vec<_>>();
+		if winners.is_empty() {
+			stakes.into_iter().for_each(|(player, stake)| {
+				T::Currency::unreserve(player, stake);
+			});
+			return
+		}
+
+		// Stakes are slashed straight from the reserved balances, which locks and the existential
+		// deposit do not apply to. A part of a stake no longer reserved cannot be collected, so
+		// it is left out of the pot rather than taken from the free balance of the player.
+		let mut pot = NegativeImbalanceOf::<T>::zero();
+		for (player, stake) in stakes {
+			let (slashed, _uncollected) = T::Currency::slash_reserved(player, stake);
+			pot.subsume(slashed);
+		}
+		if pot.peek().is_zero() {
+			return
+		}
+
+		let pot_amount = pot.peek();
+		let (rake, mut winnings) = pot.split(T::Rake::get() * pot_amount);
+		let rake_amount = rake.peek();
+		T::Currency::resolve_creating(&T::Treasury::get(), rake);
+
+		// The first winner also takes the remainder of an uneven split
+		let share = winnings.peek() / (winners.len() as u32).into();
+		for winner in winners.iter().skip(1) {
+			let (payout, rest) = winnings.split(share);
+			T::Currency::resolve_creating(winner, payout);
+			winnings = rest;
+		}
+		T::Currency::resolve_creating(&winners[0], winnings);
+
+		Self::deposit_event(Event::PotPaidOut {
+			board_id,
+			winners: winners.to_vec(),
+			pot: pot_amount,
+			rake: rake_amount,
+		});
+	}
+
+	/// Board the account is currently playing in, if any
+	pub fn game_of(account: &T::AccountId) -> Option<T::BoardId> {
+		PlayerBoards::<T>::get(account)
+	}
+
+	/// The board game with the given id
+	pub fn board(board_id: T::BoardId) -> Option<BoardGameOf<T>> {
+		BoardGames::<T>::get(board_id)
+	}
+
+	/// Turns played on the board game with the given id, in order
+	pub fn moves(board_id: T::BoardId) -> Vec<MoveOf<T>> {
+		let turns = BoardGames::<T>::get(board_id).map(|board_game| board_game.turns);
+		(0..turns.unwrap_or_default())
+			.filter_map(|turn| Moves::<T>::get(board_id, turn))
+			.collect()
+	}
+
+	/// Result of the finished board game with the given id
+	pub fn game_result(board_id: T::BoardId) -> Option<GameResultOf<T>> {
+		GameResults::<T>::get(board_id)
+	}
+
+	/// Statistics of the games played by the account
+	pub fn player_stats(account: &T::AccountId) -> PlayerStats {
+		Stats::<T>::get(account)
+	}
+
+	/// Results of the latest games finished by the account, the most recent last
+	pub fn recent_results(account: &T::AccountId) -> Vec<(T::BoardId, GameResultOf<T>)> {
+		RecentResults::<T>::get(account)
+			.into_iter()
+			.filter_map(|board_id| Some((board_id, GameResults::<T>::get(board_id)?)))
+			.collect()
+	}
+
+	/// Player that should play the next turn of the board game
+	pub fn next_player(board_id: T::BoardId) -> Option<PlayerOf<T>> {
+		BoardGames::<T>::get(board_id)
+			.filter(|board_game| board_game.phase == GamePhase::Playing)
+			.filter(|board_game| matches!(T::Game::is_finished(&board_game.state), Finished::No))
+			.map(|board_game| T::Game::get_next_player(&board_game.state))
+	}
+
+	/// Is account waiting in the matchmaking queue?
+	pub fn is_queued(account: &T::AccountId) -> bool {
+		T::Matchmaker::is_queued(account)
+	}
 }
@@ -0,0 +1,280 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	dot4gravity::{
+		Board, BoardId, BombEnergy, Cell, Coordinates, GameConfig, GameState, HashedCoordinates,
+		LastMove, PerPlayer, PlacedBombs, PlayerIndex, Position, Seed, Side,
+	},
+	*,
+};
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use sp_std::marker::PhantomData;
+
+pub mod v1 {
+	use super::*;
+
+	/// Number of players every Dot4Gravity game was played by.
+	pub const NUM_OF_PLAYERS: usize = 2;
+	/// Width and height of every Dot4Gravity board.
+	pub const BOARD_SIZE: usize = 10;
+	/// Number of bombs every player could place.
+	pub const BOMB_AMOUNT_PER_PLAYER: u32 = 3;
+
+	/// Cell stored before stones recorded the side they were dropped from.
+	#[derive(Encode, Decode)]
+	pub enum OldCell {
+		Empty,
+		Block,
+		Stone(PlayerIndex),
+	}
+
+	/// Board stored before its size was configurable.
+	#[derive(Encode, Decode)]
+	pub struct OldBoard {
+		pub cells: [[OldCell; BOARD_SIZE]; BOARD_SIZE],
+	}
+
+	/// Last move stored by the first Dot4Gravity games.
+	#[derive(Encode, Decode)]
+	pub struct OldLastMove<Player> {
+		pub player: Player,
+		pub side: Side,
+		pub position: Position,
+	}
+
+	/// Dot4Gravity state stored before bombs were committed to their player and board.
+	#[derive(Encode, Decode)]
+	pub struct OldGameState<Player> {
+		pub seed: Seed,
+		pub board: OldBoard,
+		pub winner: Option<Player>,
+		pub next_player: Player,
+		pub players: [Player; NUM_OF_PLAYERS],
+		pub bomb_energy: [(Player, BombEnergy); NUM_OF_PLAYERS],
+		pub bombs_placed:
+			[BoundedVec<HashedCoordinates, ConstU32<BOMB_AMOUNT_PER_PLAYER>>; NUM_OF_PLAYERS],
+		pub last_move: Option<OldLastMove<Player>>,
+	}
+
+	/// Board game stored before games had phases, modes and a move log.
+	#[derive(Encode, Decode)]
+	pub struct OldBoardGame<BoardId, State, Players, BlockNumber> {
+		pub board_id: BoardId,
+		pub players: Players,
+		pub state: State,
+		pub started: BlockNumber,
+	}
+
+	pub type OldBoardGameOf<T> = OldBoardGame<
+		<T as Config>::BoardId,
+		OldGameState<<T as frame_system::Config>::AccountId>,
+		BoundedPlayersOf<T>,
+		BlockNumberFor<T>,
+	>;
+
+	/// Moves the stones of an old board onto a board of the classic size. The side the stones
+	/// were dropped from was not recorded, so they are all taken as dropped from the north.
+	fn migrate_board(old: OldBoard) -> Board {
+		let config = GameConfig::CLASSIC;
+		let mut board = Board::with_size(config.board_width, config.board_height);
+		for (row, cells) in old.cells.into_iter().enumerate() {
+			for (col, cell) in cells.into_iter().enumerate() {
+				let cell = match cell {
+					OldCell::Empty => continue,
+					OldCell::Block => Cell::Block,
+					OldCell::Stone(player) => Cell::Stone(player, Side::North),
+				};
+				board.update_cell(&Coordinates::new(row as u8, col as u8), cell);
+			}
+		}
+		board
+	}
+
+	fn migrate_state<Player>(board_id: BoardId, old: OldGameState<Player>) -> GameState<Player> {
+		GameState {
+			board_id,
+			seed: old.seed,
+			board: migrate_board(old.board),
+			winner: old.winner,
+			next_player: old.next_player,
+			config: GameConfig::CLASSIC,
+			players: PerPlayer::truncate_from(Vec::from(old.players)),
+			bomb_energy: PerPlayer::truncate_from(Vec::from(old.bomb_energy)),
+			bombs_placed: PerPlayer::truncate_from(Vec::from(
+				old.bombs_placed.map(|_| PlacedBombs::default()),
+			)),
+			last_move: old.last_move.map(|last_move| LastMove {
+				player: last_move.player,
+				side: last_move.side,
+				position: last_move.position,
+			}),
+			landed_stone: None,
+		}
+	}
+
+	/// Whether the board exists and is being played on
+	fn in_play<T: Config>(board_id: T::BoardId) -> bool {
+		matches!(
+			BoardGames::<T>::get(board_id),
+			Some(board_game) if board_game.phase == GamePhase::Playing
+		)
+	}
+
+	/// Binds the games stored by the first version of the pallet to their board, and plays them
+	/// with the classic rules. The bombs placed in them were committed to without their player
+	/// and board, so they can no longer be detonated and are cleared instead, letting their
+	/// players place them again. Boards no longer in use by their players are finished and
+	/// archived, to be pruned once their retention has passed, while the deadline of the others
+	/// starts over. Their results only name a winner when one was recorded, and no turns, as
+	/// those were not logged. The seed left over by the last game would make the layout of the
+	/// next board predictable, so it is removed.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T> OnRuntimeUpgrade for MigrateToV1<T>
+	where
+		T: Config<
+			BoardId = BoardId,
+			GameState = GameState<<T as frame_system::Config>::AccountId>,
+			GameConfig = GameConfig,
+		>,
+	{
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let now = frame_system::Pallet::<T>::current_block_number();
+			let mode = T::GameModes::get()
+				.iter()
+				.position(|config| *config == GameConfig::CLASSIC)
+				.unwrap_or_default() as GameMode;
+			let mut reads = 1_u64;
+			let mut writes = 1_u64;
+			let mut finished = Vec::new();
+
+			BoardGames::<T>::translate::<OldBoardGameOf<T>, _>(|board_id, old| {
+				let in_use = old
+					.players
+					.iter()
+					.any(|player| PlayerBoards::<T>::get(player) == Some(board_id));
+				reads.saturating_accrue(1 + old.players.len() as u64);
+				writes.saturating_inc();
+				let state = migrate_state(board_id, old.state);
+				if !in_use {
+					let result = GameResultOf::<T> {
+						players: old.players.clone(),
+						winners: BoundedVec::truncate_from(state.winners()),
+						started: old.started,
+						finished: now,
+						turns: 0,
+					};
+					finished.push((board_id, result));
+				}
+				Some(BoardGame {
+					board_id: old.board_id,
+					players: old.players,
+					teams: BoundedVec::truncate_from(state.teams()),
+					state,
+					started: old.started,
+					last_turn: now,
+					phase: if in_use { GamePhase::Playing } else { GamePhase::Finished },
+					mode,
+					turns: 0,
+				})
+			});
+			// Boards that could not be translated are removed, as they can no longer be played
+			let undecodable = BoardGames::<T>::iter_keys()
+				.filter(|board_id| {
+					reads.saturating_inc();
+					BoardGames::<T>::try_get(board_id).is_err()
+				})
+				.collect::<Vec<_>>();
+			for board_id in undecodable {
+				BoardGames::<T>::remove(board_id);
+				writes.saturating_inc();
+			}
+
+			let prune_at = now.saturating_add(T::BoardRetention::get());
+			PruneQueueRange::<T>::mutate(|(_, end)| {
+				for (board_id, result) in finished {
+					GameResults::<T>::insert(board_id, result);
+					PruneQueue::<T>::insert(*end, (board_id, prune_at));
+					end.saturating_inc();
+					writes.saturating_accrue(2);
+				}
+			});
+			reads.saturating_inc();
+			writes.saturating_inc();
+
+			let released = PlayerBoards::<T>::iter()
+				.filter(|(_, board_id)| {
+					reads.saturating_accrue(2);
+					!in_play::<T>(*board_id)
+				})
+				.map(|(player, _)| player)
+				.collect::<Vec<_>>();
+			for player in released {
+				PlayerBoards::<T>::remove(player);
+				writes.saturating_inc();
+			}
+
+			GameSeed::<T>::kill();
+			writes.saturating_inc();
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(reads, writes)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			use frame_support::storage::unhashed;
+
+			// Only the boards decoding as old ones are kept, the others being removed, unless the
+			// upgrade already ran
+			let upgraded = Pallet::<T>::on_chain_storage_version() >= 1;
+			let decodable = BoardGames::<T>::iter_keys()
+				.filter(|board_id| {
+					let key = BoardGames::<T>::hashed_key_for(board_id);
+					upgraded || unhashed::get::<OldBoardGameOf<T>>(&key).is_some()
+				})
+				.count() as u32;
+			Ok(decodable.encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+			let games = u32::decode(&mut state.as_slice()).map_err(|_| "invalid game count")?;
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage version not updated");
+			ensure!(
+				BoardGames::<T>::iter_keys().count() as u32 == games,
+				"decodable games lost or undecodable games left in the upgrade"
+			);
+			ensure!(
+				PlayerBoards::<T>::iter().all(|(_, board_id)| in_play::<T>(board_id)),
+				"players left on a board not in play"
+			);
+			ensure!(
+				BoardGames::<T>::iter()
+					.filter(|(_, board_game)| board_game.phase == GamePhase::Finished)
+					.all(|(board_id, _)| GameResults::<T>::contains_key(board_id)),
+				"finished boards left without a result"
+			);
+			ensure!(GameSeed::<T>::get().is_none(), "seed of the next game left");
+			Ok(())
+		}
+	}
+}
@@ -14,18 +14,25 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{self as pallet_ajuna_board};
-use frame_support::parameter_types;
+use crate::{self as pallet_ajuna_board, dot4gravity::GameConfig};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU64, Randomness},
+};
 use frame_system::mocking::{MockBlock, MockUncheckedExtrinsic};
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	traits::{BlakeTwo256, Hash, IdentityLookup},
+	BuildStorage, Permill,
 };
 use sp_std::prelude::*;
 
 type MockAccountId = u32;
+type MockBalance = u64;
+
+pub const TREASURY: MockAccountId = 99;
+pub const INITIAL_BALANCE: MockBalance = 1_000;
 
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -34,6 +41,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = MockUncheckedExtrinsic<Test>,
 	{
 		System: frame_system,
+		Balances: pallet_balances,
 		AjunaMatchmaker: pallet_ajuna_matchmaker,
 		AjunaBoard: pallet_ajuna_board,
 	}
@@ -62,7 +70,7 @@ impl frame_system::Config for Test {
 	type DbWeight = ();
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<MockBalance>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -71,12 +79,55 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type Balance = MockBalance;
+	type DustRemoval = ();
+	type RuntimeEvent = RuntimeEvent;
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type HoldIdentifier = ();
+	type FreezeIdentifier = ();
+	type MaxHolds = ();
+	type MaxFreezes = ();
+}
+
 impl pallet_ajuna_matchmaker::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type MaxPartySize = Players;
+	type MaxInvitations = Players;
+	type WeightInfo = ();
 }
 
 parameter_types! {
-	pub const Players: u8 = 2;
+	pub GameModes: Vec<GameConfig> = vec![
+		GameConfig::CLASSIC,
+		GameConfig::QUICK,
+		GameConfig::FREE_FOR_ALL,
+		GameConfig::TEAMS,
+	];
+	pub const Players: u8 = 4;
+	pub const IdleBoardTimeout: u64 = 10;
+	pub StakeTiers: Vec<MockBalance> = vec![10, 100];
+	pub const Rake: Permill = Permill::from_percent(10);
+	pub const Treasury: MockAccountId = TREASURY;
+	pub static CommitReveal: bool = false;
+	pub const BoardRetention: u64 = 20;
+	pub static MaxRecentResults: u32 = 2;
+	pub const MaxMoves: u32 = 128;
+}
+
+/// Randomness that only changes with the subject and the block number
+pub struct MockRandomness;
+
+impl Randomness<H256, u64> for MockRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		let block_number = System::block_number();
+		(BlakeTwo256::hash_of(&(subject, block_number)), block_number)
+	}
 }
 
 impl pallet_ajuna_board::Config for Test {
@@ -85,13 +136,32 @@ impl pallet_ajuna_board::Config for Test {
 	type BoardId = u32;
 	type PlayersTurn = crate::types::Turn;
 	type GameState = crate::dot4gravity::GameState<MockAccountId>;
+	type TurnError = crate::dot4gravity::GameError;
+	type GameConfig = GameConfig;
 	type Game = crate::dot4gravity::Game<MockAccountId>;
+	type GameModes = GameModes;
 	type Players = Players;
+	type IdleBoardTimeout = IdleBoardTimeout;
+	type Currency = Balances;
+	type StakeTiers = StakeTiers;
+	type Rake = Rake;
+	type Treasury = Treasury;
+	type Randomness = MockRandomness;
+	type CommitReveal = CommitReveal;
+	type BoardRetention = BoardRetention;
+	type MaxRecentResults = MaxRecentResults;
+	type MaxMoves = MaxMoves;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let config = GenesisConfig { system: Default::default() };
+	let config = GenesisConfig {
+		system: Default::default(),
+		balances: BalancesConfig {
+			balances: (1..=5).map(|account| (account, INITIAL_BALANCE)).collect(),
+		},
+	};
 	let mut ext: sp_io::TestExternalities = config.build_storage().unwrap().into();
 	ext.execute_with(|| System::set_block_number(1));
 	ext
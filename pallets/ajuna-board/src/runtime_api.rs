@@ -0,0 +1,58 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{dot4gravity::LegalMove, types::PlayerStats};
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Runtime API to query the board games and the players in them
+	pub trait AjunaBoardApi<AccountId, BoardId, BoardGame, Move, GameResult>
+	where
+		AccountId: Codec,
+		BoardId: Codec,
+		BoardGame: Codec,
+		Move: Codec,
+		GameResult: Codec,
+	{
+		/// Board the account is currently playing in, if any
+		fn game_of(account: AccountId) -> Option<BoardId>;
+		/// The board game with the given id
+		fn board(board_id: BoardId) -> Option<BoardGame>;
+		/// Turns played on the board game with the given id, in order
+		fn moves(board_id: BoardId) -> Vec<Move>;
+		/// Result of the finished board game with the given id
+		fn game_result(board_id: BoardId) -> Option<GameResult>;
+		/// Statistics of the games played by the account
+		fn player_stats(account: AccountId) -> PlayerStats;
+		/// Results of the latest games finished by the account, the most recent last
+		fn recent_results(account: AccountId) -> Vec<(BoardId, GameResult)>;
+		/// Player that should play the next turn of the board game
+		fn next_player(board_id: BoardId) -> Option<AccountId>;
+		/// Is account waiting in the matchmaking queue?
+		fn is_queued(account: AccountId) -> bool;
+	}
+
+	/// Runtime API to query the moves available in Dot4Gravity games
+	pub trait Dot4GravityApi<AccountId, BoardId>
+	where
+		AccountId: Codec,
+		BoardId: Codec,
+	{
+		/// Every move the account can make in its turn on the board game
+		fn legal_moves(board_id: BoardId, account: AccountId) -> Vec<LegalMove>;
+	}
+}
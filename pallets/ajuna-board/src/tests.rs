@@ -15,12 +15,25 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{dot4gravity::*, mock::*, *};
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Hooks, LockableCurrency, WithdrawReasons},
+	weights::Weight,
+};
+use pallet_ajuna_matchmaker::{Ratings, INITIAL_RATING};
+use sp_core::H256;
 
 const ALICE: u32 = 1;
 const BOB: u32 = 2;
+const CHARLIE: u32 = 3;
+const DAVE: u32 = 4;
 const ERIN: u32 = 5;
 
+const CLASSIC: GameMode = 0;
+const QUICK: GameMode = 1;
+const FREE_FOR_ALL: GameMode = 2;
+const TEAMS: GameMode = 3;
+
 const BOARD_ID: u32 = 0;
 const TEST_COORD: Coordinates = Coordinates::new(0, 0);
 // The seed below generates the following board, where o is empty and x is block:
@@ -39,11 +52,37 @@ const TEST_SEED: u32 = 7357;
 #[test]
 fn queue_works() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE)));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
 		System::assert_last_event(RuntimeEvent::AjunaMatchmaker(
 			pallet_ajuna_matchmaker::Event::Queued(ALICE),
 		));
-		assert_noop!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE)), Error::<Test>::AlreadyQueued);
+		assert_noop!(
+			AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC),
+			Error::<Test>::AlreadyQueued
+		);
+	});
+}
+
+#[test]
+fn queue_matches_players_within_their_rating_band() {
+	new_test_ext().execute_with(|| {
+		Ratings::<Test>::insert(ALICE, 2_000);
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		assert!(PlayerBoards::<Test>::get(ALICE).is_none());
+		assert!(PlayerBoards::<Test>::get(BOB).is_none());
+
+		Ratings::<Test>::insert(ERIN, 2_150);
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ERIN), None, CLASSIC));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameCreated {
+			board_id: BOARD_ID,
+			players: vec![ALICE, ERIN],
+		}));
+		assert_noop!(
+			AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC),
+			Error::<Test>::AlreadyInGame
+		);
+		assert_ok!(AjunaBoard::unqueue(RuntimeOrigin::signed(BOB)));
 	});
 }
 
@@ -57,8 +96,8 @@ fn queue_creates_game_on_successful_match() {
 		assert_eq!(NextBoardId::<Test>::get(), BOARD_ID);
 
 		// queue twice to matchmake
-		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE)));
-		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB)));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
 
 		let players = vec![ALICE, BOB];
 		System::assert_has_event(RuntimeEvent::AjunaMatchmaker(
@@ -76,13 +115,39 @@ fn queue_creates_game_on_successful_match() {
 	});
 }
 
+#[test]
+fn create_game_seeds_boards_with_randomness() {
+	new_test_ext().execute_with(|| {
+		let board_of = |board_id| BoardGames::<Test>::get(board_id).unwrap().state.board;
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ERIN), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(DAVE), None, CLASSIC));
+		assert_ne!(board_of(BOARD_ID), board_of(BOARD_ID + 1));
+
+		// a stored seed overrides the randomness of the next game only
+		assert_ok!(AjunaBoard::forfeit(RuntimeOrigin::signed(ALICE)));
+		assert_ok!(AjunaBoard::forfeit(RuntimeOrigin::signed(ERIN)));
+		GameSeed::<Test>::put(TEST_SEED);
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		assert_eq!(board_of(BOARD_ID + 2), Game::new_game(ALICE, BOB, Some(TEST_SEED)).board);
+		assert!(GameSeed::<Test>::get().is_none());
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ERIN), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(DAVE), None, CLASSIC));
+		assert_ne!(board_of(BOARD_ID + 3), board_of(BOARD_ID + 2));
+	});
+}
+
 #[test]
 fn play_works() {
 	new_test_ext().execute_with(|| {
 		let salt = HashSalt::from_low_u64_be(17);
 		GameSeed::<Test>::put(TEST_SEED);
-		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB)));
-		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ERIN)));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ERIN), None, CLASSIC));
 		assert_noop!(
 			AjunaBoard::play(RuntimeOrigin::signed(ALICE), Turn::PlaceBomb(TEST_COORD, salt)),
 			Error::<Test>::NotPlaying
@@ -128,16 +193,1100 @@ fn play_works() {
 		// check if game has finished
 		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameFinished {
 			board_id: BOARD_ID,
-			winner: BOB,
+			winners: vec![BOB],
 		}));
-		assert_ne!(GameSeed::<Test>::get(), Some(TEST_SEED));
+		assert!(GameSeed::<Test>::get().is_none());
+		System::assert_has_event(RuntimeEvent::AjunaMatchmaker(
+			pallet_ajuna_matchmaker::Event::RatingUpdated(BOB, INITIAL_RATING, 1_516),
+		));
+		System::assert_has_event(RuntimeEvent::AjunaMatchmaker(
+			pallet_ajuna_matchmaker::Event::RatingUpdated(ERIN, INITIAL_RATING, 1_484),
+		));
 		assert!(PlayerBoards::<Test>::get(ALICE).is_none());
 		assert!(PlayerBoards::<Test>::get(BOB).is_none());
-		assert!(BoardGames::<Test>::get(BOARD_ID).is_some());
+		System::assert_has_event(RuntimeEvent::AjunaBoard(crate::Event::TurnPlayed {
+			board_id: BOARD_ID,
+			player: ERIN,
+			turn: Turn::DropStone((Side::North, 9)),
+		}));
+
+		// every turn is logged for replays
+		let moves = AjunaBoard::moves(BOARD_ID);
+		assert_eq!(
+			moves.first(),
+			Some(&Move {
+				player: BOB,
+				turn: Turn::PlaceBomb(Coordinates::new(9, 9), salt),
+				block: System::block_number(),
+			})
+		);
+		assert_eq!(moves.last().map(|logged| logged.player), Some(BOB));
+		assert_eq!(AjunaBoard::game_result(BOARD_ID).unwrap().turns, moves.len() as u32);
 
 		// We clear the board
 		assert_ok!(AjunaBoard::clear_board(RuntimeOrigin::root(), BOARD_ID));
 
 		assert!(BoardGames::<Test>::get(BOARD_ID).is_none());
+		assert_eq!(Moves::<Test>::iter_prefix(BOARD_ID).count(), 0);
 	})
 }
+
+#[test]
+fn play_reports_why_a_turn_was_rejected() {
+	new_test_ext().execute_with(|| {
+		let salt = HashSalt::from_low_u64_be(17);
+		GameSeed::<Test>::put(TEST_SEED);
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+
+		assert_noop!(
+			AjunaBoard::play(RuntimeOrigin::signed(BOB), Turn::DropStone((Side::North, 0))),
+			Error::<Test>::NotPlayerTurn
+		);
+		assert_noop!(
+			AjunaBoard::play(RuntimeOrigin::signed(ALICE), Turn::DropStone((Side::West, 8))),
+			Error::<Test>::InvalidStonePosition
+		);
+		assert_noop!(
+			AjunaBoard::play(
+				RuntimeOrigin::signed(ALICE),
+				Turn::DetonateBomb(TEST_COORD, salt, PowerLevel::One)
+			),
+			Error::<Test>::InvalidBombCoordinates
+		);
+	});
+}
+
+#[test]
+fn play_draws_the_game_when_the_board_is_blocked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), Some(10), CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), Some(10), CLASSIC));
+
+		// Only the north edge of column 5 is left free and no bomb energy remains
+		BoardGames::<Test>::mutate(BOARD_ID, |board_game| {
+			let state = &mut board_game.as_mut().unwrap().state;
+			for row in 0..10 {
+				for col in 0..10 {
+					let cell = if row == 0 || col == 0 || row == 9 || col == 9 {
+						Cell::Stone((row + col) % 2, Side::North)
+					} else {
+						Cell::Empty
+					};
+					state.board.update_cell(&Coordinates::new(row, col), cell);
+				}
+			}
+			state.board.update_cell(&Coordinates::new(0, 5), Cell::Empty);
+			state.board.update_cell(&Coordinates::new(1, 5), Cell::Block);
+			state.bomb_energy.iter_mut().for_each(|(_, energy)| *energy = 0);
+		});
+
+		assert_ok!(AjunaBoard::play(
+			RuntimeOrigin::signed(ALICE),
+			Turn::DropStone((Side::North, 5))
+		));
+		System::assert_has_event(RuntimeEvent::AjunaMatchmaker(
+			pallet_ajuna_matchmaker::Event::RatingUpdated(ALICE, INITIAL_RATING, INITIAL_RATING),
+		));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameDrawn {
+			board_id: BOARD_ID,
+			players: vec![ALICE, BOB],
+		}));
+
+		// the pot of 20 is split after a rake of 2
+		System::assert_has_event(RuntimeEvent::AjunaBoard(crate::Event::PotPaidOut {
+			board_id: BOARD_ID,
+			winners: vec![ALICE, BOB],
+			pot: 20,
+			rake: 2,
+		}));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 1);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 1);
+		assert_eq!(Balances::free_balance(TREASURY), 2);
+		assert_eq!(
+			AjunaBoard::player_stats(&ALICE),
+			PlayerStats { played: 1, draws: 1, ..Default::default() }
+		);
+		assert!(PlayerBoards::<Test>::get(ALICE).is_none());
+		assert!(PlayerBoards::<Test>::get(BOB).is_none());
+		let board_game = BoardGames::<Test>::get(BOARD_ID).unwrap();
+		assert_eq!(
+			<Test as Config>::Game::is_finished(&board_game.state),
+			Finished::Draw,
+			"\n{}",
+			board_game.state
+		);
+	});
+}
+
+#[test]
+fn play_draws_the_game_when_its_move_log_is_full() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+
+		// every turn but the last one the log can hold has already been played
+		BoardGames::<Test>::mutate(BOARD_ID, |board_game| {
+			board_game.as_mut().unwrap().turns = MaxMoves::get() - 1;
+		});
+
+		assert_ok!(AjunaBoard::play(
+			RuntimeOrigin::signed(ALICE),
+			Turn::PlaceBomb(TEST_COORD, HashSalt::from_low_u64_be(17))
+		));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameDrawn {
+			board_id: BOARD_ID,
+			players: vec![ALICE, BOB],
+		}));
+		assert!(PlayerBoards::<Test>::get(ALICE).is_none());
+		assert!(PlayerBoards::<Test>::get(BOB).is_none());
+		assert_eq!(BoardGames::<Test>::get(BOARD_ID).unwrap().phase, GamePhase::Finished);
+		assert_eq!(AjunaBoard::next_player(BOARD_ID), None);
+		assert_eq!(AjunaBoard::game_result(BOARD_ID).unwrap().turns, MaxMoves::get());
+	});
+}
+
+#[test]
+fn pay_out_collects_the_stake_of_a_player_whose_balance_is_locked() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), Some(10), CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), Some(10), CLASSIC));
+		Balances::set_lock(*b"testlock", &ALICE, INITIAL_BALANCE, WithdrawReasons::all());
+
+		assert_ok!(AjunaBoard::forfeit(RuntimeOrigin::signed(ALICE)));
+		System::assert_has_event(RuntimeEvent::AjunaBoard(crate::Event::PotPaidOut {
+			board_id: BOARD_ID,
+			winners: vec![BOB],
+			pot: 20,
+			rake: 2,
+		}));
+		assert_eq!(Balances::total_balance(&ALICE), INITIAL_BALANCE - 10);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE + 8);
+		assert_eq!(Balances::free_balance(TREASURY), 2);
+	});
+}
+
+#[test]
+fn claim_timeout_works_after_deadline() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+
+		// ALICE plays first, so BOB is waiting on her
+		let deadline = System::block_number() + IdleBoardTimeout::get();
+		System::set_block_number(deadline + 1);
+		assert_noop!(
+			AjunaBoard::claim_timeout(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::CannotClaimOwnTurn
+		);
+		assert_ok!(AjunaBoard::claim_timeout(RuntimeOrigin::signed(BOB)));
+
+		System::assert_has_event(RuntimeEvent::AjunaBoard(crate::Event::GameTimedOut {
+			board_id: BOARD_ID,
+			player: ALICE,
+		}));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameFinished {
+			board_id: BOARD_ID,
+			winners: vec![BOB],
+		}));
+		assert!(PlayerBoards::<Test>::get(ALICE).is_none());
+		assert!(PlayerBoards::<Test>::get(BOB).is_none());
+		let board_game = BoardGames::<Test>::get(BOARD_ID).unwrap();
+		assert_eq!(<Test as Config>::Game::is_finished(&board_game.state), Finished::Winner(BOB));
+	});
+}
+
+#[test]
+fn claim_timeout_should_not_work_before_deadline() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AjunaBoard::claim_timeout(RuntimeOrigin::signed(BOB)),
+			Error::<Test>::NotPlaying
+		);
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+
+		let deadline = System::block_number() + IdleBoardTimeout::get();
+		System::set_block_number(deadline);
+		assert_noop!(
+			AjunaBoard::claim_timeout(RuntimeOrigin::signed(BOB)),
+			Error::<Test>::TurnNotTimedOut
+		);
+
+		// playing a turn resets the deadline for the next player
+		assert_ok!(AjunaBoard::play(
+			RuntimeOrigin::signed(ALICE),
+			Turn::PlaceBomb(TEST_COORD, HashSalt::from_low_u64_be(17))
+		));
+		System::set_block_number(deadline + IdleBoardTimeout::get());
+		assert_noop!(
+			AjunaBoard::claim_timeout(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::TurnNotTimedOut
+		);
+		assert!(PlayerBoards::<Test>::get(ALICE).is_some());
+		assert!(PlayerBoards::<Test>::get(BOB).is_some());
+	});
+}
+
+#[test]
+fn commit_reveal_seeds_the_board_with_the_revealed_secrets() {
+	new_test_ext().execute_with(|| {
+		CommitReveal::set(true);
+		let (alice_secret, bob_secret) = (H256::repeat_byte(1), H256::repeat_byte(2));
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		let board_game = BoardGames::<Test>::get(BOARD_ID).unwrap();
+		assert_eq!(board_game.phase, GamePhase::AwaitingCommitments);
+		assert!(AjunaBoard::next_player(BOARD_ID).is_none());
+		assert_noop!(
+			AjunaBoard::play(RuntimeOrigin::signed(ALICE), Turn::DropStone((Side::North, 0))),
+			Error::<Test>::InvalidPhase
+		);
+		assert_noop!(
+			AjunaBoard::reveal(RuntimeOrigin::signed(ALICE), alice_secret),
+			Error::<Test>::InvalidPhase
+		);
+
+		let alice_commitment = AjunaBoard::commitment_of(BOARD_ID, &alice_secret, &ALICE);
+		assert_ok!(AjunaBoard::commit(RuntimeOrigin::signed(ALICE), alice_commitment));
+		assert_noop!(
+			AjunaBoard::commit(RuntimeOrigin::signed(ALICE), alice_commitment),
+			Error::<Test>::AlreadyCommitted
+		);
+		assert_ok!(AjunaBoard::commit(
+			RuntimeOrigin::signed(BOB),
+			AjunaBoard::commitment_of(BOARD_ID, &bob_secret, &BOB)
+		));
+		assert_eq!(BoardGames::<Test>::get(BOARD_ID).unwrap().phase, GamePhase::AwaitingReveals);
+
+		// secrets only open the commitment of the player who made it
+		assert_noop!(
+			AjunaBoard::reveal(RuntimeOrigin::signed(ALICE), bob_secret),
+			Error::<Test>::InvalidReveal
+		);
+		assert_ok!(AjunaBoard::reveal(RuntimeOrigin::signed(ALICE), alice_secret));
+		assert_noop!(
+			AjunaBoard::reveal(RuntimeOrigin::signed(ALICE), alice_secret),
+			Error::<Test>::AlreadyRevealed
+		);
+		assert_ok!(AjunaBoard::reveal(RuntimeOrigin::signed(BOB), bob_secret));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameStarted {
+			board_id: BOARD_ID,
+		}));
+
+		let seed = u32::from_le_bytes([3; 4]);
+		let board_game = BoardGames::<Test>::get(BOARD_ID).unwrap();
+		assert_eq!(board_game.phase, GamePhase::Playing);
+		assert_eq!(
+			board_game.state.board,
+			Game::new_game(ALICE, BOB, Some(seed)).board,
+			"\n{}",
+			board_game.state.board
+		);
+		assert_eq!(AjunaBoard::next_player(BOARD_ID), Some(ALICE));
+		assert_eq!(Commitments::<Test>::iter_prefix(BOARD_ID).count(), 0);
+		assert_eq!(Reveals::<Test>::iter_prefix(BOARD_ID).count(), 0);
+	});
+}
+
+#[test]
+fn commit_reveal_seeds_boards_with_secrets_of_the_whole_range() {
+	new_test_ext().execute_with(|| {
+		CommitReveal::set(true);
+		let (alice_secret, bob_secret) = (H256::repeat_byte(0xf0), H256::repeat_byte(0x0f));
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		for (player, secret) in [(ALICE, alice_secret), (BOB, bob_secret)] {
+			let commitment = AjunaBoard::commitment_of(BOARD_ID, &secret, &player);
+			assert_ok!(AjunaBoard::commit(RuntimeOrigin::signed(player), commitment));
+		}
+		assert_ok!(AjunaBoard::reveal(RuntimeOrigin::signed(ALICE), alice_secret));
+		assert_ok!(AjunaBoard::reveal(RuntimeOrigin::signed(BOB), bob_secret));
+
+		let board = BoardGames::<Test>::get(BOARD_ID).unwrap().state.board;
+		assert_eq!(board, Game::new_game(ALICE, BOB, Some(u32::MAX)).board);
+		assert_ne!(board, Game::new_game(ALICE, BOB, Some(u32::from_le_bytes([3; 4]))).board);
+	});
+}
+
+#[test]
+fn reveal_rejects_commitments_made_for_another_board() {
+	new_test_ext().execute_with(|| {
+		CommitReveal::set(true);
+		let (alice_secret, bob_secret) = (H256::repeat_byte(1), H256::repeat_byte(2));
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		assert_ok!(AjunaBoard::commit(
+			RuntimeOrigin::signed(ALICE),
+			AjunaBoard::commitment_of(BOARD_ID + 1, &alice_secret, &ALICE)
+		));
+		assert_ok!(AjunaBoard::commit(
+			RuntimeOrigin::signed(BOB),
+			AjunaBoard::commitment_of(BOARD_ID, &bob_secret, &BOB)
+		));
+
+		assert_noop!(
+			AjunaBoard::reveal(RuntimeOrigin::signed(ALICE), alice_secret),
+			Error::<Test>::InvalidReveal
+		);
+		assert_ok!(AjunaBoard::reveal(RuntimeOrigin::signed(BOB), bob_secret));
+	});
+}
+
+#[test]
+fn claim_timeout_forfeits_the_game_of_a_player_withholding_their_reveal() {
+	new_test_ext().execute_with(|| {
+		CommitReveal::set(true);
+		let (alice_secret, bob_secret) = (H256::repeat_byte(1), H256::repeat_byte(2));
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), Some(10), CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), Some(10), CLASSIC));
+		assert_ok!(AjunaBoard::commit(
+			RuntimeOrigin::signed(ALICE),
+			AjunaBoard::commitment_of(BOARD_ID, &alice_secret, &ALICE)
+		));
+		assert_ok!(AjunaBoard::commit(
+			RuntimeOrigin::signed(BOB),
+			AjunaBoard::commitment_of(BOARD_ID, &bob_secret, &BOB)
+		));
+		assert_ok!(AjunaBoard::reveal(RuntimeOrigin::signed(ALICE), alice_secret));
+
+		// BOB learnt the secret of ALICE and withholds their own
+		let deadline = System::block_number() + IdleBoardTimeout::get();
+		System::set_block_number(deadline);
+		assert_noop!(
+			AjunaBoard::claim_timeout(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::TurnNotTimedOut
+		);
+		System::set_block_number(deadline + 1);
+		assert_noop!(
+			AjunaBoard::claim_timeout(RuntimeOrigin::signed(BOB)),
+			Error::<Test>::CannotClaimOwnTurn
+		);
+		assert_ok!(AjunaBoard::claim_timeout(RuntimeOrigin::signed(ALICE)));
+
+		System::assert_has_event(RuntimeEvent::AjunaBoard(crate::Event::GameTimedOut {
+			board_id: BOARD_ID,
+			player: BOB,
+		}));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameFinished {
+			board_id: BOARD_ID,
+			winners: vec![ALICE],
+		}));
+		assert!(PlayerBoards::<Test>::get(ALICE).is_none());
+		assert!(PlayerBoards::<Test>::get(BOB).is_none());
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 8);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 10);
+		assert_eq!(Commitments::<Test>::iter_prefix(BOARD_ID).count(), 0);
+		assert_eq!(Reveals::<Test>::iter_prefix(BOARD_ID).count(), 0);
+	});
+}
+
+#[test]
+fn claim_timeout_forfeits_the_game_of_a_player_withholding_their_commitment() {
+	new_test_ext().execute_with(|| {
+		CommitReveal::set(true);
+		let alice_secret = H256::repeat_byte(1);
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), Some(10), CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), Some(10), CLASSIC));
+		assert_ok!(AjunaBoard::commit(
+			RuntimeOrigin::signed(ALICE),
+			AjunaBoard::commitment_of(BOARD_ID, &alice_secret, &ALICE)
+		));
+
+		System::set_block_number(System::block_number() + IdleBoardTimeout::get() + 1);
+		assert_noop!(
+			AjunaBoard::claim_timeout(RuntimeOrigin::signed(BOB)),
+			Error::<Test>::CannotClaimOwnTurn
+		);
+		assert_ok!(AjunaBoard::claim_timeout(RuntimeOrigin::signed(ALICE)));
+
+		System::assert_has_event(RuntimeEvent::AjunaBoard(crate::Event::GameTimedOut {
+			board_id: BOARD_ID,
+			player: BOB,
+		}));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameFinished {
+			board_id: BOARD_ID,
+			winners: vec![ALICE],
+		}));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 8);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 10);
+		assert_eq!(Commitments::<Test>::iter_prefix(BOARD_ID).count(), 0);
+	});
+}
+
+#[test]
+fn claim_timeout_cancels_a_board_nobody_seeded_in_time() {
+	new_test_ext().execute_with(|| {
+		CommitReveal::set(true);
+		let (alice_secret, bob_secret) = (H256::repeat_byte(1), H256::repeat_byte(2));
+
+		// nobody commits to a secret
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), Some(10), CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), Some(10), CLASSIC));
+		let deadline = System::block_number() + IdleBoardTimeout::get();
+		System::set_block_number(deadline);
+		assert_noop!(
+			AjunaBoard::claim_timeout(RuntimeOrigin::signed(ALICE)),
+			Error::<Test>::TurnNotTimedOut
+		);
+		System::set_block_number(deadline + 1);
+		assert_ok!(AjunaBoard::claim_timeout(RuntimeOrigin::signed(ALICE)));
+
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameCancelled {
+			board_id: BOARD_ID,
+		}));
+		assert!(BoardGames::<Test>::get(BOARD_ID).is_none());
+		for player in [ALICE, BOB] {
+			assert!(PlayerBoards::<Test>::get(player).is_none());
+			assert!(PlayerStakes::<Test>::get(player).is_none());
+			assert_eq!(Balances::free_balance(player), INITIAL_BALANCE);
+			assert_eq!(Balances::reserved_balance(player), 0);
+			assert_eq!(AjunaBoard::player_stats(&player), PlayerStats::default());
+		}
+
+		// everybody commits to a secret but nobody reveals it
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), Some(10), CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), Some(10), CLASSIC));
+		assert_ok!(AjunaBoard::commit(
+			RuntimeOrigin::signed(ALICE),
+			AjunaBoard::commitment_of(BOARD_ID + 1, &alice_secret, &ALICE)
+		));
+		assert_ok!(AjunaBoard::commit(
+			RuntimeOrigin::signed(BOB),
+			AjunaBoard::commitment_of(BOARD_ID + 1, &bob_secret, &BOB)
+		));
+		System::set_block_number(System::block_number() + IdleBoardTimeout::get() + 1);
+		assert_ok!(AjunaBoard::claim_timeout(RuntimeOrigin::signed(BOB)));
+
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameCancelled {
+			board_id: BOARD_ID + 1,
+		}));
+		assert!(BoardGames::<Test>::get(BOARD_ID + 1).is_none());
+		assert_eq!(Commitments::<Test>::iter_prefix(BOARD_ID + 1).count(), 0);
+		for player in [ALICE, BOB] {
+			assert!(PlayerBoards::<Test>::get(player).is_none());
+			assert_eq!(Balances::free_balance(player), INITIAL_BALANCE);
+			assert_eq!(Balances::reserved_balance(player), 0);
+		}
+	});
+}
+
+#[test]
+fn forfeit_gives_the_win_to_the_opponent() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(AjunaBoard::forfeit(RuntimeOrigin::signed(ALICE)), Error::<Test>::NotPlaying);
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		assert_ok!(AjunaBoard::forfeit(RuntimeOrigin::signed(ALICE)));
+
+		System::assert_has_event(RuntimeEvent::AjunaBoard(crate::Event::GameForfeited {
+			board_id: BOARD_ID,
+			player: ALICE,
+			winners: vec![BOB],
+		}));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameFinished {
+			board_id: BOARD_ID,
+			winners: vec![BOB],
+		}));
+		assert!(PlayerBoards::<Test>::get(ALICE).is_none());
+		assert!(PlayerBoards::<Test>::get(BOB).is_none());
+		let board_game = BoardGames::<Test>::get(BOARD_ID).unwrap();
+		assert_eq!(<Test as Config>::Game::is_finished(&board_game.state), Finished::Winner(BOB));
+
+		// the game is over for both players
+		assert_noop!(AjunaBoard::forfeit(RuntimeOrigin::signed(BOB)), Error::<Test>::NotPlaying);
+		assert_ok!(AjunaBoard::clear_board(RuntimeOrigin::root(), BOARD_ID));
+	});
+}
+
+#[test]
+fn finished_boards_are_archived_and_pruned_after_their_retention() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		assert_ok!(AjunaBoard::play(
+			RuntimeOrigin::signed(ALICE),
+			Turn::PlaceBomb(TEST_COORD, HashSalt::from_low_u64_be(17))
+		));
+		System::set_block_number(5);
+		assert_ok!(AjunaBoard::forfeit(RuntimeOrigin::signed(BOB)));
+
+		assert_eq!(
+			AjunaBoard::game_result(BOARD_ID),
+			Some(GameResult {
+				players: vec![ALICE, BOB].try_into().unwrap(),
+				winners: vec![ALICE].try_into().unwrap(),
+				started: 1,
+				finished: 5,
+				turns: 1,
+			})
+		);
+		assert_eq!(PruneQueueRange::<Test>::get(), (0, 1));
+
+		// the board is kept for clients to look at until its retention has passed
+		let prune_at = 5 + BoardRetention::get();
+		AjunaBoard::on_idle(prune_at - 1, Weight::MAX);
+		assert!(BoardGames::<Test>::contains_key(BOARD_ID));
+		AjunaBoard::on_idle(prune_at, Weight::zero());
+		assert!(BoardGames::<Test>::contains_key(BOARD_ID));
+
+		assert_eq!(AjunaBoard::moves(BOARD_ID).len(), 1);
+		AjunaBoard::on_idle(prune_at, Weight::MAX);
+		assert!(!BoardGames::<Test>::contains_key(BOARD_ID));
+		assert_eq!(Moves::<Test>::iter_prefix(BOARD_ID).count(), 0);
+		assert!(AjunaBoard::game_result(BOARD_ID).is_some());
+		assert_eq!(PruneQueueRange::<Test>::get(), (1, 1));
+	});
+}
+
+#[test]
+fn player_stats_track_the_results_of_finished_games() {
+	new_test_ext().execute_with(|| {
+		let play_game = |loser| {
+			assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+			assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+			assert_ok!(AjunaBoard::forfeit(RuntimeOrigin::signed(loser)));
+		};
+		play_game(BOB);
+		play_game(BOB);
+		assert_eq!(
+			AjunaBoard::player_stats(&ALICE),
+			PlayerStats { played: 2, wins: 2, win_streak: 2, ..Default::default() }
+		);
+		assert_eq!(
+			AjunaBoard::player_stats(&BOB),
+			PlayerStats { played: 2, losses: 2, forfeits: 2, ..Default::default() }
+		);
+
+		// only the most recent results are kept
+		play_game(ALICE);
+		assert_eq!(
+			AjunaBoard::player_stats(&ALICE),
+			PlayerStats { played: 3, wins: 2, losses: 1, forfeits: 1, win_streak: 0, draws: 0 }
+		);
+		assert_eq!(AjunaBoard::player_stats(&BOB).win_streak, 1);
+		let recent_boards = |account| {
+			AjunaBoard::recent_results(&account)
+				.into_iter()
+				.map(|(board_id, _)| board_id)
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(recent_boards(ALICE), vec![BOARD_ID + 1, BOARD_ID + 2]);
+		assert_eq!(recent_boards(BOB), vec![BOARD_ID + 1, BOARD_ID + 2]);
+		assert_eq!(AjunaBoard::recent_results(&ALICE)[1].1.winners.to_vec(), vec![BOB]);
+		assert_eq!(AjunaBoard::player_stats(&ERIN), PlayerStats::default());
+	});
+}
+
+#[test]
+fn player_stats_are_tracked_without_keeping_recent_results() {
+	new_test_ext().execute_with(|| {
+		MaxRecentResults::set(0);
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		assert_ok!(AjunaBoard::forfeit(RuntimeOrigin::signed(BOB)));
+
+		assert_eq!(AjunaBoard::player_stats(&ALICE).wins, 1);
+		assert!(AjunaBoard::recent_results(&ALICE).is_empty());
+		assert!(AjunaBoard::recent_results(&BOB).is_empty());
+	});
+}
+
+#[test]
+fn unqueue_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(AjunaBoard::unqueue(RuntimeOrigin::signed(ALICE)), Error::<Test>::NotQueued);
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::unqueue(RuntimeOrigin::signed(ALICE)));
+		System::assert_last_event(RuntimeEvent::AjunaMatchmaker(
+			pallet_ajuna_matchmaker::Event::Dequeued(ALICE),
+		));
+		assert_noop!(AjunaBoard::unqueue(RuntimeOrigin::signed(ALICE)), Error::<Test>::NotQueued);
+
+		// ALICE is no longer around to be matched with
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		assert!(PlayerBoards::<Test>::get(BOB).is_none());
+		assert!(BoardGames::<Test>::get(BOARD_ID).is_none());
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameCreated {
+			board_id: BOARD_ID,
+			players: vec![BOB, ALICE],
+		}));
+	});
+}
+
+#[test]
+fn runtime_api_queries_work() {
+	new_test_ext().execute_with(|| {
+		assert!(AjunaBoard::game_of(&ALICE).is_none());
+		assert!(AjunaBoard::board(BOARD_ID).is_none());
+		assert!(AjunaBoard::next_player(BOARD_ID).is_none());
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert!(AjunaBoard::is_queued(&ALICE));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		assert!(!AjunaBoard::is_queued(&ALICE));
+
+		assert_eq!(AjunaBoard::game_of(&ALICE), Some(BOARD_ID));
+		assert_eq!(AjunaBoard::game_of(&BOB), Some(BOARD_ID));
+		assert_eq!(AjunaBoard::board(BOARD_ID).unwrap().players.to_vec(), vec![ALICE, BOB]);
+		assert_eq!(AjunaBoard::next_player(BOARD_ID), Some(ALICE));
+
+		assert_ok!(AjunaBoard::forfeit(RuntimeOrigin::signed(BOB)));
+		assert!(AjunaBoard::game_of(&ALICE).is_none());
+		assert!(AjunaBoard::board(BOARD_ID).is_some());
+		assert!(AjunaBoard::next_player(BOARD_ID).is_none());
+	});
+}
+
+#[test]
+fn queue_with_stake_only_matches_players_with_the_same_stake() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AjunaBoard::queue(RuntimeOrigin::signed(ALICE), Some(7), CLASSIC),
+			Error::<Test>::InvalidStake
+		);
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), Some(10), CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), Some(100), CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ERIN), None, CLASSIC));
+		assert!(BoardGames::<Test>::get(BOARD_ID).is_none());
+		assert_eq!(Balances::reserved_balance(ALICE), 10);
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		assert_eq!(Balances::reserved_balance(ERIN), 0);
+
+		assert_ok!(AjunaBoard::unqueue(RuntimeOrigin::signed(BOB)));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert!(PlayerStakes::<Test>::get(BOB).is_none());
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), Some(10), CLASSIC));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameCreated {
+			board_id: BOARD_ID,
+			players: vec![ALICE, BOB],
+		}));
+		assert!(AjunaBoard::is_queued(&ERIN));
+	});
+}
+
+#[test]
+fn queue_only_matches_players_of_the_same_game_mode() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AjunaBoard::queue(
+				RuntimeOrigin::signed(ALICE),
+				None,
+				GameModes::get().len() as GameMode
+			),
+			Error::<Test>::InvalidGameMode
+		);
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, QUICK));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+		assert!(BoardGames::<Test>::get(BOARD_ID).is_none());
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ERIN), None, QUICK));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameCreated {
+			board_id: BOARD_ID,
+			players: vec![ALICE, ERIN],
+		}));
+		assert!(AjunaBoard::is_queued(&BOB));
+
+		let board_game = AjunaBoard::board(BOARD_ID).unwrap();
+		assert_eq!(board_game.mode, QUICK);
+		assert_eq!(board_game.state.config, GameConfig::QUICK);
+		assert_eq!(board_game.state.board.width(), 8);
+		assert_eq!(board_game.state.board.height(), 8);
+		assert!(QueuedBrackets::<Test>::get(ALICE).is_none());
+		assert!(QueuedBrackets::<Test>::get(ERIN).is_none());
+	});
+}
+
+#[test]
+fn queue_matches_as_many_players_as_their_game_mode_is_played_by() {
+	new_test_ext().execute_with(|| {
+		GameSeed::<Test>::put(TEST_SEED);
+		for player in [ALICE, BOB, CHARLIE] {
+			assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(player), None, FREE_FOR_ALL));
+		}
+		assert!(BoardGames::<Test>::get(BOARD_ID).is_none());
+
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(DAVE), None, FREE_FOR_ALL));
+		let players = vec![ALICE, BOB, CHARLIE, DAVE];
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameCreated {
+			board_id: BOARD_ID,
+			players: players.clone(),
+		}));
+		assert!(players.iter().all(|player| PlayerBoards::<Test>::get(player) == Some(BOARD_ID)));
+
+		let salt = HashSalt::from_low_u64_be(17);
+		for player in players {
+			assert_eq!(AjunaBoard::next_player(BOARD_ID), Some(player));
+			assert_ok!(AjunaBoard::play(
+				RuntimeOrigin::signed(player),
+				Turn::PlaceBomb(TEST_COORD, salt)
+			));
+		}
+		assert_eq!(AjunaBoard::next_player(BOARD_ID), Some(ALICE));
+	});
+}
+
+#[test]
+fn team_games_are_won_by_the_squares_of_the_whole_team() {
+	new_test_ext().execute_with(|| {
+		for player in [ALICE, BOB, CHARLIE, DAVE] {
+			assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(player), None, TEAMS));
+		}
+		assert_eq!(AjunaBoard::board(BOARD_ID).unwrap().teams.to_vec(), vec![0, 1, 0, 1]);
+
+		// Alice and Charlie have two squares mixing their stones, and a third one to complete
+		BoardGames::<Test>::mutate(BOARD_ID, |board_game| {
+			let state = &mut board_game.as_mut().unwrap().state;
+			let a = Cell::Stone(0, Side::North);
+			let c = Cell::Stone(2, Side::North);
+			state.board = Board::new();
+			for (col, cells) in [(a, c), (c, a), (a, c), (c, Cell::Empty)].into_iter().enumerate() {
+				state.board.update_cell(&Coordinates::new(8, col as u8), cells.0);
+				state.board.update_cell(&Coordinates::new(9, col as u8), cells.1);
+			}
+		});
+
+		assert_ok!(AjunaBoard::play(
+			RuntimeOrigin::signed(ALICE),
+			Turn::DropStone((Side::East, 9))
+		));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameFinished {
+			board_id: BOARD_ID,
+			winners: vec![ALICE, CHARLIE],
+		}));
+		assert_eq!(
+			AjunaBoard::game_result(BOARD_ID).unwrap().winners.to_vec(),
+			vec![ALICE, CHARLIE]
+		);
+		assert_eq!(AjunaBoard::player_stats(&CHARLIE).wins, 1);
+		assert_eq!(AjunaBoard::player_stats(&DAVE).losses, 1);
+	});
+}
+
+#[test]
+fn free_for_all_games_rate_every_player_by_their_place_in_the_ranking() {
+	new_test_ext().execute_with(|| {
+		for player in [ALICE, BOB, CHARLIE, DAVE] {
+			assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(player), None, FREE_FOR_ALL));
+		}
+
+		// Bob has two squares and a third one to complete, Charlie has one, the others none
+		BoardGames::<Test>::mutate(BOARD_ID, |board_game| {
+			let state = &mut board_game.as_mut().unwrap().state;
+			let b = Cell::Stone(1, Side::North);
+			let c = Cell::Stone(2, Side::North);
+			state.board = Board::new();
+			for col in 0..4 {
+				state.board.update_cell(&Coordinates::new(8, col), b);
+			}
+			for col in 0..3 {
+				state.board.update_cell(&Coordinates::new(9, col), b);
+			}
+			for (row, col) in [(4, 4), (4, 5), (5, 4), (5, 5)] {
+				state.board.update_cell(&Coordinates::new(row, col), c);
+			}
+			state.next_player = BOB;
+		});
+
+		assert_ok!(AjunaBoard::play(RuntimeOrigin::signed(BOB), Turn::DropStone((Side::East, 9))));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameFinished {
+			board_id: BOARD_ID,
+			winners: vec![BOB],
+		}));
+		assert_eq!(Ratings::<Test>::get(BOB), 1_532);
+		assert_eq!(Ratings::<Test>::get(CHARLIE), INITIAL_RATING);
+		assert_eq!(Ratings::<Test>::get(ALICE), 1_468);
+		assert_eq!(Ratings::<Test>::get(DAVE), 1_468);
+
+		// only the winner counts as such in the statistics
+		assert_eq!(AjunaBoard::player_stats(&BOB).wins, 1);
+		for loser in [ALICE, CHARLIE, DAVE] {
+			assert_eq!(AjunaBoard::player_stats(&loser).losses, 1);
+		}
+	});
+}
+
+#[test]
+fn forfeiting_a_team_game_forfeits_it_for_the_whole_team() {
+	new_test_ext().execute_with(|| {
+		for player in [ALICE, BOB, CHARLIE, DAVE] {
+			assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(player), None, TEAMS));
+		}
+
+		assert_ok!(AjunaBoard::forfeit(RuntimeOrigin::signed(DAVE)));
+		System::assert_has_event(RuntimeEvent::AjunaBoard(crate::Event::GameForfeited {
+			board_id: BOARD_ID,
+			player: DAVE,
+			winners: vec![ALICE, CHARLIE],
+		}));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameFinished {
+			board_id: BOARD_ID,
+			winners: vec![ALICE, CHARLIE],
+		}));
+		assert_eq!(AjunaBoard::player_stats(&BOB).losses, 1);
+		assert_eq!(AjunaBoard::player_stats(&CHARLIE).wins, 1);
+		assert!(Ratings::<Test>::get(ALICE) > INITIAL_RATING);
+		assert!(Ratings::<Test>::get(DAVE) < INITIAL_RATING);
+		assert_eq!(BoardGames::<Test>::get(BOARD_ID).unwrap().phase, GamePhase::Finished);
+	});
+}
+
+#[test]
+fn forfeiting_a_free_for_all_game_gives_the_win_to_every_other_player() {
+	new_test_ext().execute_with(|| {
+		for player in [ALICE, BOB, CHARLIE, DAVE] {
+			assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(player), Some(10), FREE_FOR_ALL));
+		}
+
+		assert_ok!(AjunaBoard::forfeit(RuntimeOrigin::signed(BOB)));
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameFinished {
+			board_id: BOARD_ID,
+			winners: vec![ALICE, CHARLIE, DAVE],
+		}));
+		for winner in [ALICE, CHARLIE, DAVE] {
+			assert_eq!(AjunaBoard::player_stats(&winner).wins, 1);
+			assert!(Ratings::<Test>::get(winner) > INITIAL_RATING);
+		}
+		assert_eq!(AjunaBoard::player_stats(&BOB).losses, 1);
+		assert!(Ratings::<Test>::get(BOB) < INITIAL_RATING);
+
+		// the pot of 40 is split between the winners after a rake of 4
+		System::assert_has_event(RuntimeEvent::AjunaBoard(crate::Event::PotPaidOut {
+			board_id: BOARD_ID,
+			winners: vec![ALICE, CHARLIE, DAVE],
+			pot: 40,
+			rake: 4,
+		}));
+		let board_game = BoardGames::<Test>::get(BOARD_ID).unwrap();
+		assert_eq!(board_game.phase, GamePhase::Finished);
+		assert!(!matches!(<Test as Config>::Game::is_finished(&board_game.state), Finished::No));
+	});
+}
+
+#[test]
+fn queue_party_matches_the_party_as_a_whole() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AjunaMatchmaker::create_party(RuntimeOrigin::signed(ALICE)));
+		for member in [CHARLIE, ERIN] {
+			assert_ok!(AjunaMatchmaker::invite(RuntimeOrigin::signed(ALICE), member));
+			assert_ok!(AjunaMatchmaker::accept_invitation(RuntimeOrigin::signed(member), ALICE));
+		}
+		assert_noop!(
+			AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC),
+			Error::<Test>::InParty
+		);
+		assert_noop!(
+			AjunaBoard::queue_party(RuntimeOrigin::signed(CHARLIE), TEAMS),
+			Error::<Test>::NotPartyLeader
+		);
+		assert_noop!(
+			AjunaBoard::queue_party(RuntimeOrigin::signed(ALICE), CLASSIC),
+			Error::<Test>::PartyTooLarge
+		);
+
+		assert_ok!(AjunaMatchmaker::leave_party(RuntimeOrigin::signed(ERIN)));
+		assert_ok!(AjunaBoard::queue_party(RuntimeOrigin::signed(ALICE), TEAMS));
+		assert!(AjunaBoard::is_queued(&CHARLIE));
+		// the party stays together while queued, so its stakes stay with its queue
+		assert_noop!(
+			AjunaMatchmaker::leave_party(RuntimeOrigin::signed(CHARLIE)),
+			pallet_ajuna_matchmaker::Error::<Test>::AlreadyQueued
+		);
+		assert_ok!(AjunaBoard::unqueue(RuntimeOrigin::signed(CHARLIE)));
+		assert!(!AjunaBoard::is_queued(&ALICE));
+		assert!(QueuedBrackets::<Test>::get(ALICE).is_none());
+
+		assert_ok!(AjunaBoard::queue_party(RuntimeOrigin::signed(ALICE), TEAMS));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, TEAMS));
+		assert!(BoardGames::<Test>::get(BOARD_ID).is_none());
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(DAVE), None, TEAMS));
+
+		// the members of the party are seated in the same team
+		System::assert_last_event(RuntimeEvent::AjunaBoard(crate::Event::GameCreated {
+			board_id: BOARD_ID,
+			players: vec![ALICE, BOB, CHARLIE, DAVE],
+		}));
+		let board_game = AjunaBoard::board(BOARD_ID).unwrap();
+		assert_eq!(board_game.teams.to_vec(), vec![0, 1, 0, 1]);
+		assert_eq!(board_game.state.players.to_vec(), vec![ALICE, BOB, CHARLIE, DAVE]);
+		assert!(QueuedBrackets::<Test>::get(CHARLIE).is_none());
+	});
+}
+
+#[test]
+fn finish_game_pays_the_pot_to_the_winner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), Some(100), CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), Some(100), CLASSIC));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE - 100);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 100);
+
+		assert_ok!(AjunaBoard::forfeit(RuntimeOrigin::signed(BOB)));
+		System::assert_has_event(RuntimeEvent::AjunaBoard(crate::Event::PotPaidOut {
+			board_id: BOARD_ID,
+			winners: vec![ALICE],
+			pot: 200,
+			rake: 20,
+		}));
+		assert_eq!(Balances::free_balance(ALICE), INITIAL_BALANCE + 80);
+		assert_eq!(Balances::free_balance(BOB), INITIAL_BALANCE - 100);
+		assert_eq!(Balances::free_balance(TREASURY), 20);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert!(PlayerStakes::<Test>::get(ALICE).is_none());
+		assert!(PlayerStakes::<Test>::get(BOB).is_none());
+	});
+}
+
+#[test]
+fn migration_to_v1_binds_games_to_their_board_and_clears_their_bombs() {
+	use crate::migration::v1::{
+		MigrateToV1, OldBoard, OldBoardGame, OldBoardGameOf, OldCell, OldGameState, OldLastMove,
+	};
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	let old_board_game = |board_id: u32, players: [u32; 2]| -> OldBoardGameOf<Test> {
+		let mut cells: [[OldCell; 10]; 10] =
+			core::array::from_fn(|_| core::array::from_fn(|_| OldCell::Empty));
+		cells[2][2] = OldCell::Block;
+		cells[9][3] = OldCell::Stone(0);
+		OldBoardGame {
+			board_id,
+			players: BoundedVec::truncate_from(players.to_vec()),
+			state: OldGameState {
+				seed: TEST_SEED,
+				board: OldBoard { cells },
+				winner: None,
+				next_player: players[1],
+				players,
+				bomb_energy: [(players[0], 5), (players[1], 5)],
+				bombs_placed: [
+					BoundedVec::truncate_from(vec![H256::repeat_byte(1)]),
+					BoundedVec::default(),
+				],
+				last_move: Some(OldLastMove { player: players[0], side: Side::North, position: 3 }),
+			},
+			started: 1,
+		}
+	};
+
+	new_test_ext().execute_with(|| {
+		let salt = HashSalt::from_low_u64_be(17);
+		for (board_id, players) in [(BOARD_ID, [ALICE, BOB]), (BOARD_ID + 1, [CHARLIE, DAVE])] {
+			frame_support::storage::unhashed::put(
+				&BoardGames::<Test>::hashed_key_for(board_id),
+				&old_board_game(board_id, players),
+			);
+		}
+		// Only the first board is still in use, while Erin is stuck on a board that is gone
+		PlayerBoards::<Test>::insert(ALICE, BOARD_ID);
+		PlayerBoards::<Test>::insert(BOB, BOARD_ID);
+		PlayerBoards::<Test>::insert(ERIN, BOARD_ID + 7);
+		GameSeed::<Test>::put(TEST_SEED);
+		StorageVersion::new(0).put::<AjunaBoard>();
+		System::set_block_number(5);
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(AjunaBoard::on_chain_storage_version(), 1);
+
+		let mut board = Board::with_size(10, 10);
+		board.update_cell(&Coordinates::new(2, 2), Cell::Block);
+		board.update_cell(&Coordinates::new(9, 3), Cell::Stone(0, Side::North));
+		let migrated = BoardGames::<Test>::get(BOARD_ID).unwrap();
+		assert_eq!(migrated.board_id, BOARD_ID);
+		assert_eq!(migrated.players.to_vec(), vec![ALICE, BOB]);
+		assert_eq!(migrated.teams.to_vec(), vec![0, 1]);
+		assert_eq!(migrated.started, 1);
+		assert_eq!(migrated.last_turn, 5);
+		assert_eq!(migrated.phase, GamePhase::Playing);
+		assert_eq!(migrated.mode, CLASSIC);
+		assert_eq!(migrated.turns, 0);
+		assert_eq!(migrated.state.board_id, BOARD_ID);
+		assert_eq!(migrated.state.seed, TEST_SEED);
+		assert_eq!(migrated.state.board, board);
+		assert_eq!(migrated.state.winner, None);
+		assert_eq!(migrated.state.next_player, BOB);
+		assert_eq!(migrated.state.config, GameConfig::CLASSIC);
+		assert_eq!(migrated.state.players.to_vec(), vec![ALICE, BOB]);
+		assert_eq!(migrated.state.bomb_energy.to_vec(), vec![(ALICE, 5), (BOB, 5)]);
+		assert_eq!(migrated.state.bombs_placed.len(), 2);
+		assert!(migrated.state.bombs_placed.iter().all(|bombs| bombs.is_empty()));
+		assert_eq!(
+			migrated.state.last_move,
+			Some(LastMove { player: ALICE, side: Side::North, position: 3 })
+		);
+		assert_eq!(migrated.state.landed_stone, None);
+		assert_eq!(BoardGames::<Test>::get(BOARD_ID + 1).unwrap().phase, GamePhase::Finished);
+		assert_eq!(
+			AjunaBoard::game_result(BOARD_ID + 1),
+			Some(GameResult {
+				players: vec![CHARLIE, DAVE].try_into().unwrap(),
+				winners: Default::default(),
+				started: 1,
+				finished: 5,
+				turns: 0,
+			})
+		);
+		assert_eq!(AjunaBoard::game_result(BOARD_ID), None);
+		assert_eq!(PruneQueueRange::<Test>::get(), (0, 1));
+		assert_eq!(PruneQueue::<Test>::get(0), Some((BOARD_ID + 1, 5 + BoardRetention::get())));
+
+		assert_eq!(PlayerBoards::<Test>::get(ALICE), Some(BOARD_ID));
+		assert_eq!(PlayerBoards::<Test>::get(BOB), Some(BOARD_ID));
+		assert_eq!(PlayerBoards::<Test>::get(ERIN), None);
+		assert_eq!(GameSeed::<Test>::get(), None);
+
+		assert_ok!(AjunaBoard::play(RuntimeOrigin::signed(BOB), Turn::PlaceBomb(TEST_COORD, salt)));
+		assert_ok!(AjunaBoard::play(
+			RuntimeOrigin::signed(ALICE),
+			Turn::PlaceBomb(TEST_COORD, salt)
+		));
+		assert_ok!(AjunaBoard::play(
+			RuntimeOrigin::signed(BOB),
+			Turn::DetonateBomb(TEST_COORD, salt, PowerLevel::One)
+		));
+	});
+}
+
+#[test]
+fn hits_can_only_be_claimed_in_games_with_hidden_bombs() {
+	new_test_ext().execute_with(|| {
+		let salt = HashSalt::from_low_u64_be(17);
+		GameSeed::<Test>::put(TEST_SEED);
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(ALICE), None, CLASSIC));
+		assert_ok!(AjunaBoard::queue(RuntimeOrigin::signed(BOB), None, CLASSIC));
+
+		let commitment = Game::hash_coordinates(TEST_COORD, salt, &ALICE, BOARD_ID);
+		assert_ok!(AjunaBoard::play(RuntimeOrigin::signed(ALICE), Turn::HideBomb(commitment)));
+		assert_ok!(AjunaBoard::play(
+			RuntimeOrigin::signed(BOB),
+			Turn::DropStone((Side::North, TEST_COORD.col))
+		));
+		assert_noop!(
+			AjunaBoard::play(RuntimeOrigin::signed(ALICE), Turn::ClaimHit(TEST_COORD, salt)),
+			Error::<Test>::NoHitToClaim
+		);
+		assert_ok!(AjunaBoard::play(
+			RuntimeOrigin::signed(ALICE),
+			Turn::DetonateBomb(TEST_COORD, salt, PowerLevel::One)
+		));
+		assert_eq!(
+			AjunaBoard::board(BOARD_ID).unwrap().state.board.get_cell(&TEST_COORD),
+			Cell::Empty
+		);
+	});
+}
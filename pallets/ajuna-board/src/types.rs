@@ -16,41 +16,157 @@
 
 use super::*;
 use crate::dot4gravity::{Game as Dot4Gravity, *};
-use sp_std::borrow::ToOwned;
 
-pub(crate) type PlayerOf<T> = <<T as Config>::Game as TurnBasedGame>::Player;
-pub(crate) type BoundedPlayersOf<T> =
+/// Number of bits the stake tier is shifted by in the matchmaking bracket, leaving the lower bits
+/// to the rating band of the player
+pub const STAKE_TIER_SHIFT: Bracket = 24;
+/// Number of bits the game mode is shifted by in the matchmaking bracket, between the stake tier
+/// and the rating band of the player
+pub const GAME_MODE_SHIFT: Bracket = 16;
+
+/// Index of the rules a game is played with, among the game modes of the pallet
+pub type GameMode = u8;
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+	<T as frame_system::Config>::AccountId,
+>>::NegativeImbalance;
+pub type PlayerOf<T> = <<T as Config>::Game as TurnBasedGame>::Player;
+pub type BoundedPlayersOf<T> =
 	BoundedVec<<T as frame_system::Config>::AccountId, <T as Config>::Players>;
-pub(crate) type BoardGameOf<T> = BoardGame<
+pub type BoundedTeamsOf<T> = BoundedVec<TeamIndex, <T as Config>::Players>;
+pub type MoveOf<T> =
+	Move<<T as frame_system::Config>::AccountId, <T as Config>::PlayersTurn, BlockNumberFor<T>>;
+pub type BoardGameOf<T> = BoardGame<
 	<T as Config>::BoardId,
 	<T as Config>::GameState,
 	BoundedPlayersOf<T>,
+	BoundedTeamsOf<T>,
 	BlockNumberFor<T>,
 >;
+pub type GameResultOf<T> = GameResult<BoundedPlayersOf<T>, BlockNumberFor<T>>;
+pub type RecentResultsOf<T> = BoundedVec<<T as Config>::BoardId, <T as Config>::MaxRecentResults>;
+
+/// Statistics of the games played by an account
+#[derive(Clone, Debug, Default, Encode, Decode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerStats {
+	/// Number of games finished
+	pub played: u32,
+	/// Number of games won
+	pub wins: u32,
+	/// Number of games lost
+	pub losses: u32,
+	/// Number of games drawn
+	pub draws: u32,
+	/// Number of games lost by forfeiting or timing out
+	pub forfeits: u32,
+	/// Number of games won in a row, up to the latest
+	pub win_streak: u32,
+}
+
+/// Phases a board game goes through
+#[derive(Clone, Copy, Debug, Encode, Decode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum GamePhase {
+	/// Players have to commit to a secret for the seed of the board
+	AwaitingCommitments,
+	/// Players have to reveal the secret they committed to
+	AwaitingReveals,
+	/// The board is built and players take their turns
+	Playing,
+	/// The game is over and its board is kept until it is pruned
+	Finished,
+}
+
+/// A turn played in a board game
+#[derive(Clone, Debug, Encode, Decode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct Move<Player, Turn, BlockNumber> {
+	/// Player who played the turn
+	pub player: Player,
+	/// The turn played
+	pub turn: Turn,
+	/// Block in which the turn was played
+	pub block: BlockNumber,
+}
 
 /// The state of the board game
 #[derive(Clone, Debug, Encode, Decode, TypeInfo, MaxEncodedLen)]
-pub struct BoardGame<BoardId, State, Players, Start> {
-	board_id: BoardId,
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct BoardGame<BoardId, State, Players, Teams, BlockNumber> {
+	pub(crate) board_id: BoardId,
 	/// Players in the game
 	pub(crate) players: Players,
+	/// Team of each player, in the order of the players
+	pub(crate) teams: Teams,
 	/// The current state of the game
 	pub state: State,
 	/// When the game started
-	pub started: Start,
+	pub started: BlockNumber,
+	/// When the last turn was played, or the current phase started
+	pub last_turn: BlockNumber,
+	/// The current phase of the game
+	pub phase: GamePhase,
+	/// Game mode whose rules the game is played with
+	pub mode: GameMode,
+	/// Number of turns played so far, which are kept in the move log of the board
+	pub turns: u32,
 }
 
-impl<BoardId, State, Players, Start> BoardGame<BoardId, State, Players, Start> {
+impl<BoardId, State, Players, Teams, BlockNumber: Clone>
+	BoardGame<BoardId, State, Players, Teams, BlockNumber>
+{
 	/// Create a BoardGame
-	pub(crate) fn new(board_id: BoardId, players: Players, state: State, started: Start) -> Self {
-		Self { board_id, players, state, started }
+	pub(crate) fn new(
+		board_id: BoardId,
+		players: Players,
+		teams: Teams,
+		state: State,
+		started: BlockNumber,
+		phase: GamePhase,
+		mode: GameMode,
+	) -> Self {
+		Self {
+			board_id,
+			players,
+			teams,
+			state,
+			last_turn: started.clone(),
+			started,
+			phase,
+			mode,
+			turns: 0,
+		}
 	}
 }
 
+/// Compact record of a finished board game, kept after its board is pruned
+#[derive(Clone, Debug, Encode, Decode, Eq, PartialEq, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct GameResult<Players, BlockNumber> {
+	/// Players in the game
+	pub players: Players,
+	/// Players who won the game, none if it was drawn
+	pub winners: Players,
+	/// When the game started
+	pub started: BlockNumber,
+	/// When the game finished
+	pub finished: BlockNumber,
+	/// Number of turns played
+	pub turns: u32,
+}
+
 #[derive(Debug, PartialEq)]
 pub enum Finished<Player> {
 	No,
 	Winner(Player),
+	/// Game has ended without a winner
+	Draw,
+	/// Game has ended with the players ranked from first to last place, the winners first and
+	/// the players tied for a place together
+	Ranked(Vec<Vec<Player>>),
 }
 
 pub trait TurnBasedGame {
@@ -60,29 +176,51 @@ pub trait TurnBasedGame {
 	type Player: Clone;
 	/// The state of the game
 	type State: Codec;
-	/// Initialise turn based game with players returning the initial state
-	fn init(players: &[Self::Player], seed: Option<u32>) -> Option<Self::State>;
+	/// The reason a turn was rejected
+	type TurnError;
+	/// Rules the game is played with, chosen when it is created
+	type Config;
+	/// Identifies the board the game is played on
+	type BoardId;
+	/// Initialise turn based game on a board with players and rules returning the initial state
+	fn init(
+		board_id: Self::BoardId,
+		players: &[Self::Player],
+		seed: Option<u32>,
+		config: Self::Config,
+	) -> Option<Self::State>;
+	/// Number of players a game played with the rules is created for
+	fn players(config: &Self::Config) -> u32;
+	/// Team of each player, in the order the game was initialised with
+	fn teams(state: &Self::State) -> Vec<TeamIndex>;
 	/// Get the player that played its turn last
 	fn get_last_player(state: &Self::State) -> Self::Player;
 	/// Get the player that should play its turn next
 	fn get_next_player(state: &Self::State) -> Self::Player;
-	/// Play a turn with player on the current state returning the new state
-	fn play_turn(player: Self::Player, state: Self::State, turn: Self::Turn)
-		-> Option<Self::State>;
+	/// Play a turn with player on the current state returning the new state, or the reason the
+	/// turn was rejected
+	fn play_turn(
+		player: Self::Player,
+		state: Self::State,
+		turn: Self::Turn,
+	) -> Result<Self::State, Self::TurnError>;
 	/// Forces the termination of a game with a designated winner, useful when games
 	/// get stalled for some reason.
 	fn abort(state: Self::State, winner: Self::Player) -> Self::State;
-	/// Check if the game has finished with winner
+	/// Check if the game has finished, either with a winner or a draw
 	fn is_finished(state: &Self::State) -> Finished<Self::Player>;
 	/// Get seed if any
 	fn seed(state: &Self::State) -> Option<u32>;
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum Turn {
 	PlaceBomb(Coordinates, HashSalt),
 	DetonateBomb(Coordinates, HashSalt, PowerLevel),
 	DropStone((Side, u8)),
+	HideBomb(HashedCoordinates),
+	ClaimHit(Coordinates, HashSalt),
 }
 
 impl<Account> TurnBasedGame for Game<Account>
@@ -92,15 +230,31 @@ where
 	type Turn = Turn;
 	type Player = Account;
 	type State = GameState<Account>;
-
-	fn init(players: &[Self::Player], seed: Option<u32>) -> Option<Self::State> {
-		if let [player_1, player_2] = players {
-			Some(Dot4Gravity::new_game(player_1.to_owned(), player_2.to_owned(), seed))
+	type TurnError = GameError;
+	type Config = GameConfig;
+	type BoardId = BoardId;
+
+	fn init(
+		board_id: Self::BoardId,
+		players: &[Self::Player],
+		seed: Option<u32>,
+		config: Self::Config,
+	) -> Option<Self::State> {
+		if config.is_valid() {
+			Dot4Gravity::new_game_with_config(board_id, players.to_vec(), seed, config).ok()
 		} else {
 			None
 		}
 	}
 
+	fn players(config: &Self::Config) -> u32 {
+		config.players.into()
+	}
+
+	fn teams(state: &Self::State) -> Vec<TeamIndex> {
+		state.teams()
+	}
+
 	fn get_last_player(state: &Self::State) -> Self::Player {
 		state
 			.last_move
@@ -117,15 +271,18 @@ where
 		player: Self::Player,
 		state: Self::State,
 		turn: Self::Turn,
-	) -> Option<Self::State> {
+	) -> Result<Self::State, Self::TurnError> {
 		match turn {
 			Turn::PlaceBomb(coordinates, salt) =>
 				Dot4Gravity::place_bomb(state, player, coordinates, salt),
 			Turn::DetonateBomb(coordinates, salt, power_level) =>
 				Dot4Gravity::detonate_bomb(state, player, coordinates, salt, power_level),
 			Turn::DropStone((side, pos)) => Dot4Gravity::drop_stone(state, player, side, pos),
+			Turn::HideBomb(coordinate_hash) =>
+				Dot4Gravity::hide_bomb(state, player, coordinate_hash),
+			Turn::ClaimHit(coordinates, salt) =>
+				Dot4Gravity::claim_hit(state, player, coordinates, salt),
 		}
-		.ok()
 	}
 
 	fn abort(state: Self::State, winner: Self::Player) -> Self::State {
@@ -136,7 +293,9 @@ where
 
 	fn is_finished(state: &Self::State) -> Finished<Self::Player> {
 		match state.winner.clone() {
+			Some(_) if state.players.len() > 2 => Finished::Ranked(state.standings()),
 			Some(winner) => Finished::Winner(winner),
+			None if state.is_draw() => Finished::Draw,
 			None => Finished::No,
 		}
 	}
@@ -146,6 +305,22 @@ where
 	}
 }
 
+impl<T> From<GameError> for Error<T> {
+	fn from(error: GameError) -> Self {
+		match error {
+			GameError::NoMoreBombsAvailable => Error::NoMoreBombsAvailable,
+			GameError::InsufficientBombEnergy => Error::InsufficientBombEnergy,
+			GameError::InvalidBombCoordinates => Error::InvalidBombCoordinates,
+			GameError::InvalidStonePosition => Error::InvalidStonePosition,
+			GameError::NotPlayerTurn => Error::NotPlayerTurn,
+			GameError::NoPreviousPosition => Error::NoPreviousPosition,
+			GameError::GameAlreadyFinished => Error::GameAlreadyFinished,
+			GameError::NoHitToClaim => Error::NoHitToClaim,
+			GameError::InvalidPlayers => Error::InvalidPlayers,
+		}
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -172,8 +347,16 @@ mod tests {
 		type Turn = Guess;
 		type Player = Account;
 		type State = MockGameState;
-
-		fn init(players: &[Self::Player], _seed: Option<u32>) -> Option<Self::State> {
+		type TurnError = ();
+		type Config = ();
+		type BoardId = ();
+
+		fn init(
+			_board_id: Self::BoardId,
+			players: &[Self::Player],
+			_seed: Option<u32>,
+			_config: Self::Config,
+		) -> Option<Self::State> {
 			match players.to_vec().try_into() {
 				Ok(players) => Some(MockGameState {
 					players,
@@ -185,6 +368,14 @@ mod tests {
 			}
 		}
 
+		fn players(_config: &Self::Config) -> u32 {
+			MAX_PLAYERS as u32
+		}
+
+		fn teams(state: &Self::State) -> Vec<TeamIndex> {
+			(0..state.players.len() as TeamIndex).collect()
+		}
+
 		fn get_last_player(state: &Self::State) -> Self::Player {
 			let next_player_index = (state.next_player as usize + 1) % state.players.len();
 			state.players[next_player_index]
@@ -198,12 +389,12 @@ mod tests {
 			player: Self::Player,
 			state: Self::State,
 			turn: Self::Turn,
-		) -> Option<Self::State> {
+		) -> Result<Self::State, Self::TurnError> {
 			if state.winner.is_some() ||
 				!state.players.contains(&player) ||
 				state.players[state.next_player as usize] != player
 			{
-				return None
+				return Err(())
 			}
 
 			let mut state = state;
@@ -213,7 +404,7 @@ mod tests {
 				state.winner = Some(player);
 			}
 
-			Some(state)
+			Ok(state)
 		}
 
 		fn abort(state: Self::State, winner: Self::Player) -> Self::State {
@@ -237,7 +428,7 @@ mod tests {
 
 	#[test]
 	fn guessing_works() {
-		let state = MockGame::init(&[PLAYER_1, PLAYER_2], None).unwrap();
+		let state = MockGame::init((), &[PLAYER_1, PLAYER_2], None, ()).unwrap();
 		assert_eq!(MockGame::get_next_player(&state), PLAYER_1);
 
 		let state = MockGame::play_turn(PLAYER_1, state, 1).unwrap();
@@ -248,7 +439,7 @@ mod tests {
 		assert_eq!(MockGame::is_finished(&state), Finished::Winner(PLAYER_2));
 
 		// new game
-		let state = MockGame::init(&[PLAYER_1, PLAYER_2], None).unwrap();
+		let state = MockGame::init((), &[PLAYER_1, PLAYER_2], None, ()).unwrap();
 		let state = MockGame::abort(state, PLAYER_1);
 		assert_eq!(MockGame::is_finished(&state), Finished::Winner(PLAYER_1));
 	}
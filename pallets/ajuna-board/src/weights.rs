@@ -0,0 +1,201 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Estimated weights for pallet_ajuna_board, the matchmaker included. They were not measured:
+//! each one counts the storage accesses of its benchmark in `benchmarking.rs`, which sets up the
+//! worst case of four players in the largest game mode, next to a rough execution time. The proof
+//! sizes add up the max encoded size of every key and value read, for the 32 byte accounts,
+//! parties of four, a 256 move log and the 1.5 kB boards of the runtime. Replace them with the
+//! measured weights by running `scripts/benchmark.sh` on reference hardware, which overwrites this
+//! file.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_ajuna_board.
+pub trait WeightInfo {
+	fn queue() -> Weight;
+	fn queue_party() -> Weight;
+	fn unqueue() -> Weight;
+	fn play() -> Weight;
+	fn play_turn_until_finished() -> Weight;
+	fn clear_board() -> Weight;
+	fn claim_timeout() -> Weight;
+	fn forfeit() -> Weight;
+	fn commit() -> Weight;
+	fn reveal() -> Weight;
+}
+
+/// Weights for pallet_ajuna_board using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: AjunaBoard PlayerBoards (r:5 w:4), AjunaBoard QueuedBrackets (r:0 w:5)
+	// Storage: AjunaBoard PlayerStakes (r:0 w:1), AjunaBoard NextBoardId (r:1 w:1)
+	// Storage: AjunaBoard BoardGames (r:0 w:1), RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: System Account (r:1 w:1), AjunaMatchmaker PartyOf (r:6 w:0)
+	// Storage: AjunaMatchmaker Ratings (r:1 w:0)
+	// Storage: AjunaMatchmaker PlayerQueue (r:1 w:5), AjunaMatchmaker Brackets (r:4 w:3)
+	// Storage: AjunaMatchmaker Players (r:14 w:9), AjunaMatchmaker Parties (r:8 w:0)
+	// Storage: AjunaMatchmaker Lanes (r:8 w:4)
+	// Searching the oldest tickets of each size: AjunaMatchmaker Lanes (r:4), Players (r:8)
+	fn queue() -> Weight {
+		Weight::from_parts(240_000_000, 5_858)
+			.saturating_add(T::DbWeight::get().reads(42_u64))
+			.saturating_add(T::DbWeight::get().writes(34_u64))
+	}
+	// Storage: AjunaBoard PlayerBoards (r:8 w:4), AjunaBoard QueuedBrackets (r:0 w:8)
+	// Storage: AjunaBoard NextBoardId (r:1 w:1), AjunaBoard BoardGames (r:0 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0), AjunaMatchmaker PartyOf (r:9 w:0)
+	// Storage: AjunaMatchmaker Parties (r:12 w:0), AjunaMatchmaker Ratings (r:1 w:0)
+	// Storage: AjunaMatchmaker PlayerQueue (r:4 w:8), AjunaMatchmaker Brackets (r:4 w:4)
+	// Storage: AjunaMatchmaker Players (r:14 w:6), AjunaMatchmaker Lanes (r:8 w:3)
+	// Searching the oldest tickets of each size: AjunaMatchmaker Lanes (r:4), Players (r:8)
+	fn queue_party() -> Weight {
+		Weight::from_parts(290_000_000, 6_954)
+			.saturating_add(T::DbWeight::get().reads(54_u64))
+			.saturating_add(T::DbWeight::get().writes(35_u64))
+	}
+	// Storage: AjunaBoard QueuedBrackets (r:1 w:1), AjunaBoard PlayerStakes (r:1 w:1)
+	// Storage: System Account (r:1 w:1), AjunaMatchmaker PlayerQueue (r:1 w:1)
+	// Storage: AjunaMatchmaker Players (r:2 w:2), AjunaMatchmaker Parties (r:1 w:0)
+	// Storage: AjunaMatchmaker Brackets (r:1 w:1), AjunaMatchmaker PartyOf (r:1 w:0)
+	// Storage: AjunaMatchmaker Lanes (r:1 w:1)
+	fn unqueue() -> Weight {
+		Weight::from_parts(62_000_000, 788)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	// Storage: AjunaBoard PlayerBoards (r:1 w:0), AjunaBoard BoardGames (r:1 w:1)
+	// Storage: AjunaBoard Moves (r:0 w:1)
+	fn play() -> Weight {
+		Weight::from_parts(110_000_000, 1_536)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: AjunaBoard PlayerBoards (r:1 w:4), AjunaBoard BoardGames (r:2 w:2)
+	// Storage: AjunaBoard Moves (r:0 w:1), AjunaBoard PlayerStakes (r:4 w:4)
+	// Storage: AjunaBoard GameResults (r:0 w:1), AjunaBoard Stats (r:4 w:4)
+	// Storage: AjunaBoard RecentResults (r:4 w:4), AjunaBoard PruneQueueRange (r:1 w:1)
+	// Storage: AjunaBoard PruneQueue (r:0 w:1), AjunaMatchmaker Ratings (r:4 w:4)
+	fn play_turn_until_finished() -> Weight {
+		Weight::from_parts(240_000_000, 4_104)
+			.saturating_add(T::DbWeight::get().reads(20_u64))
+			.saturating_add(T::DbWeight::get().writes(26_u64))
+	}
+	// Storage: AjunaBoard BoardGames (r:1 w:1), AjunaBoard PlayerBoards (r:4 w:0)
+	// Clearing a full move log of MaxMoves moves: AjunaBoard Moves (r:0 w:256)
+	fn clear_board() -> Weight {
+		Weight::from_parts(60_000_000, 1_644)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(257_u64))
+	}
+	// Storage: AjunaBoard PlayerBoards (r:1 w:4), AjunaBoard BoardGames (r:2 w:2)
+	// Storage: AjunaBoard PlayerStakes (r:4 w:4), AjunaBoard GameResults (r:0 w:1)
+	// Storage: AjunaBoard Stats (r:5 w:5), AjunaBoard RecentResults (r:4 w:4)
+	// Storage: AjunaBoard PruneQueueRange (r:1 w:1), AjunaBoard PruneQueue (r:0 w:1)
+	// Storage: AjunaBoard Commitments (r:0 w:4), AjunaBoard Reveals (r:0 w:4)
+	// Storage: AjunaMatchmaker Ratings (r:4 w:4), System Account (r:5 w:5)
+	fn claim_timeout() -> Weight {
+		Weight::from_parts(260_000_000, 4_796)
+			.saturating_add(T::DbWeight::get().reads(26_u64))
+			.saturating_add(T::DbWeight::get().writes(39_u64))
+	}
+	// Storage: AjunaBoard PlayerBoards (r:1 w:4), AjunaBoard BoardGames (r:2 w:2)
+	// Storage: AjunaBoard PlayerStakes (r:4 w:4), AjunaBoard GameResults (r:0 w:1)
+	// Storage: AjunaBoard Stats (r:5 w:5), AjunaBoard RecentResults (r:4 w:4)
+	// Storage: AjunaBoard PruneQueueRange (r:1 w:1), AjunaBoard PruneQueue (r:0 w:1)
+	// Storage: AjunaBoard Commitments (r:0 w:4), AjunaBoard Reveals (r:0 w:4)
+	// Storage: AjunaMatchmaker Ratings (r:4 w:4), System Account (r:5 w:5)
+	fn forfeit() -> Weight {
+		Weight::from_parts(260_000_000, 4_796)
+			.saturating_add(T::DbWeight::get().reads(26_u64))
+			.saturating_add(T::DbWeight::get().writes(39_u64))
+	}
+	// Storage: AjunaBoard PlayerBoards (r:1 w:0), AjunaBoard BoardGames (r:1 w:1)
+	// Storage: AjunaBoard Commitments (r:5 w:1)
+	fn commit() -> Weight {
+		Weight::from_parts(45_000_000, 1_876)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: AjunaBoard PlayerBoards (r:1 w:0), AjunaBoard BoardGames (r:1 w:1)
+	// Storage: AjunaBoard Commitments (r:1 w:4), AjunaBoard Reveals (r:8 w:5)
+	fn reveal() -> Weight {
+		Weight::from_parts(130_000_000, 2_148)
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn queue() -> Weight {
+		Weight::from_parts(240_000_000, 5_858)
+			.saturating_add(RocksDbWeight::get().reads(42_u64))
+			.saturating_add(RocksDbWeight::get().writes(34_u64))
+	}
+	fn queue_party() -> Weight {
+		Weight::from_parts(290_000_000, 6_954)
+			.saturating_add(RocksDbWeight::get().reads(54_u64))
+			.saturating_add(RocksDbWeight::get().writes(35_u64))
+	}
+	fn unqueue() -> Weight {
+		Weight::from_parts(62_000_000, 788)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	fn play() -> Weight {
+		Weight::from_parts(110_000_000, 1_536)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn play_turn_until_finished() -> Weight {
+		Weight::from_parts(240_000_000, 4_104)
+			.saturating_add(RocksDbWeight::get().reads(20_u64))
+			.saturating_add(RocksDbWeight::get().writes(26_u64))
+	}
+	fn clear_board() -> Weight {
+		Weight::from_parts(60_000_000, 1_644)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(257_u64))
+	}
+	fn claim_timeout() -> Weight {
+		Weight::from_parts(260_000_000, 4_796)
+			.saturating_add(RocksDbWeight::get().reads(26_u64))
+			.saturating_add(RocksDbWeight::get().writes(39_u64))
+	}
+	fn forfeit() -> Weight {
+		Weight::from_parts(260_000_000, 4_796)
+			.saturating_add(RocksDbWeight::get().reads(26_u64))
+			.saturating_add(RocksDbWeight::get().writes(39_u64))
+	}
+	fn commit() -> Weight {
+		Weight::from_parts(45_000_000, 1_876)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn reveal() -> Weight {
+		Weight::from_parts(130_000_000, 2_148)
+			.saturating_add(RocksDbWeight::get().reads(11_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+}
@@ -0,0 +1,160 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_support::assert_ok;
+use frame_system::RawOrigin;
+
+const SEED: u32 = 0;
+
+fn enqueue_players<T: Config>(how_many: u32) {
+	(0..how_many).for_each(|i| {
+		assert!(Matchmaking::<T>::enqueue(account("player", i, SEED), DEFAULT_BRACKET));
+	});
+}
+
+/// Creates the party with the given index, returning its leader
+fn create_party<T: Config>(party: u32, size: u32) -> T::AccountId {
+	let leader: T::AccountId = account("leader", party, SEED);
+	assert_ok!(Pallet::<T>::create_party(RawOrigin::Signed(leader.clone()).into()));
+	(1..size).for_each(|i| {
+		let member: T::AccountId = account("member", i, party);
+		let origin = RawOrigin::Signed(leader.clone()).into();
+		assert_ok!(Pallet::<T>::invite(origin, member.clone()));
+		let origin = RawOrigin::Signed(member).into();
+		assert_ok!(Pallet::<T>::accept_invitation(origin, leader.clone()));
+	});
+	leader
+}
+
+/// Queues as many parties of each size as fit in a match, all of which are searched for it,
+/// returning how many players they queue
+fn enqueue_parties_of_each_size<T: Config>() -> u32 {
+	let players = DEFAULT_PLAYERS as u32;
+	let mut party = 0;
+	let mut queued = 0;
+	for size in 2..=players.min(T::MaxPartySize::get()) {
+		for _ in 0..players / size {
+			let leader = create_party::<T>(party, size);
+			assert!(Matchmaking::<T>::enqueue_party(leader, DEFAULT_BRACKET));
+			party += 1;
+			queued += size;
+		}
+	}
+	queued
+}
+
+/// Invites as many accounts to the party of leader as leave room for one more invitation
+fn invite_up_to_the_last<T: Config>(leader: &T::AccountId) {
+	(1..T::MaxInvitations::get()).for_each(|i| {
+		let origin = RawOrigin::Signed(leader.clone()).into();
+		assert_ok!(Pallet::<T>::invite(origin, account("invited", i, SEED)));
+	});
+}
+
+benchmarks! {
+	enqueue {
+		let n in 0 .. 1_000;
+		enqueue_players::<T>(n);
+		let player: T::AccountId = account("player", n, SEED);
+	}: {
+		assert!(Matchmaking::<T>::enqueue(player, DEFAULT_BRACKET));
+	}
+
+	// The cost of leaving should not depend on the number of players queued around the ticket
+	dequeue {
+		let n in 1 .. 1_000;
+		enqueue_players::<T>(n);
+		let player: T::AccountId = account("player", n / 2, SEED);
+	}: {
+		assert!(Matchmaking::<T>::dequeue(player, DEFAULT_BRACKET));
+	}
+	verify {
+		assert_eq!(Brackets::<T>::get(DEFAULT_BRACKET).len(), n - 1);
+	}
+
+	// The cost of matching should not depend on the number of players already queued, only on
+	// the oldest tickets of each size searched, which fill every lane fitting in the match
+	try_match {
+		let n in 2 .. 1_000;
+		let in_parties = enqueue_parties_of_each_size::<T>();
+		enqueue_players::<T>(n);
+	}: {
+		assert!(Matchmaking::<T>::try_match(DEFAULT_BRACKET, DEFAULT_PLAYERS as u32).is_some());
+	}
+	verify {
+		let queued = in_parties + n - DEFAULT_PLAYERS as u32;
+		assert_eq!(Matchmaking::<T>::queued_players(DEFAULT_BRACKET).len() as u32, queued);
+	}
+
+	create_party {
+		let leader: T::AccountId = account("leader", 0, SEED);
+	}: _(RawOrigin::Signed(leader.clone()))
+	verify {
+		assert!(Parties::<T>::contains_key(&leader));
+	}
+
+	// The invitation is the last one the leader can have waiting
+	invite {
+		let leader = create_party::<T>(0, 1);
+		invite_up_to_the_last::<T>(&leader);
+		let member: T::AccountId = account("member", 1, SEED);
+	}: _(RawOrigin::Signed(leader.clone()), member.clone())
+	verify {
+		assert!(Invitations::<T>::get(&leader).contains(&member));
+	}
+
+	// The invitation withdrawn is the last of the most the leader can have waiting
+	uninvite {
+		let leader = create_party::<T>(0, 1);
+		invite_up_to_the_last::<T>(&leader);
+		let member: T::AccountId = account("member", 1, SEED);
+		let origin = RawOrigin::Signed(leader.clone()).into();
+		assert_ok!(Pallet::<T>::invite(origin, member.clone()));
+	}: _(RawOrigin::Signed(leader.clone()), member.clone())
+	verify {
+		assert!(!Invitations::<T>::get(&leader).contains(&member));
+	}
+
+	// Joining fills the party up to its maximum size, among the most invitations waiting
+	accept_invitation {
+		let size = T::MaxPartySize::get();
+		let leader = create_party::<T>(0, size - 1);
+		invite_up_to_the_last::<T>(&leader);
+		let member: T::AccountId = account("member", size - 1, SEED);
+		let origin = RawOrigin::Signed(leader.clone()).into();
+		assert_ok!(Pallet::<T>::invite(origin, member.clone()));
+	}: _(RawOrigin::Signed(member.clone()), leader.clone())
+	verify {
+		assert_eq!(PartyOf::<T>::get(&member), Some(leader));
+	}
+
+	// The leader leaving disbands the whole party
+	leave_party {
+		let m in 1 .. T::MaxPartySize::get();
+		let leader = create_party::<T>(0, m);
+	}: _(RawOrigin::Signed(leader.clone()))
+	verify {
+		assert!(!Parties::<T>::contains_key(&leader));
+	}
+
+	impl_benchmark_test_suite!(
+		Pallet,
+		crate::mock::new_test_ext(),
+		crate::mock::Test,
+	)
+}
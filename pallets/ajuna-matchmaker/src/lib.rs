@@ -18,17 +18,24 @@
 
 pub use pallet::*;
 pub use types::*;
+pub use weights::WeightInfo;
 
 use frame_support::pallet_prelude::*;
+use frame_system::pallet_prelude::*;
 use sp_std::vec::Vec;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
 #[cfg(test)]
 mod mock;
 
 #[cfg(test)]
 mod tests;
 
+pub mod migration;
 mod types;
+pub mod weights;
 
 #[frame_support::pallet]
 pub mod pallet {
@@ -37,9 +44,21 @@ pub mod pallet {
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Most members of a party, its leader included.
+		#[pallet::constant]
+		type MaxPartySize: Get<u32>;
+		/// Most invitations a party leader can have waiting to be accepted.
+		#[pallet::constant]
+		type MaxInvitations: Get<u32>;
+		/// Weights of the calls of the pallet
+		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	/// Map of brackets with their index
@@ -47,7 +66,20 @@ pub mod pallet {
 	pub type Brackets<T: Config> =
 		StorageMap<_, Blake2_128Concat, Bracket, BracketRange, ValueQuery>;
 
-	/// A double map indexed by bracket and account
+	/// Ends of the lane of the tickets of each size queued in a bracket, indexed by bracket and
+	/// ticket size
+	#[pallet::storage]
+	pub type Lanes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		Bracket,
+		Blake2_128Concat,
+		u32,
+		LaneRange,
+		ValueQuery,
+	>;
+
+	/// A double map of the tickets queued, indexed by bracket and their position in it
 	#[pallet::storage]
 	pub type Players<T: Config> = StorageDoubleMap<
 		_,
@@ -55,14 +87,49 @@ pub mod pallet {
 		Bracket,
 		Blake2_128Concat,
 		BracketCounter,
-		T::AccountId,
+		Ticket<T::AccountId>,
 		OptionQuery,
 	>;
 
-	/// A map tracking which accounts are queued
+	/// A map tracking which accounts are queued, with their bracket and position in it
 	#[pallet::storage]
 	pub type PlayerQueue<T: Config> =
-		StorageMap<_, Blake2_128Concat, T::AccountId, u8, OptionQuery>;
+		StorageMap<_, Blake2_128Concat, T::AccountId, (Bracket, BracketCounter), OptionQuery>;
+
+	/// Members of each party in the order they joined, indexed by the account leading it
+	#[pallet::storage]
+	pub type Parties<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::AccountId, T::MaxPartySize>,
+		OptionQuery,
+	>;
+
+	/// A map tracking which accounts are in a party, with the account leading it
+	#[pallet::storage]
+	pub type PartyOf<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId, OptionQuery>;
+
+	/// Accounts invited to a party that have yet to accept, indexed by the account leading it
+	#[pallet::storage]
+	pub type Invitations<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<T::AccountId, T::MaxInvitations>,
+		ValueQuery,
+	>;
+
+	#[pallet::type_value]
+	pub fn DefaultRating() -> Rating {
+		INITIAL_RATING
+	}
+
+	/// Skill rating of each account, updated with the results of their games
+	#[pallet::storage]
+	pub type Ratings<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, Rating, ValueQuery, DefaultRating>;
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -71,5 +138,143 @@ pub mod pallet {
 		Queued(T::AccountId),
 		/// Players are now matched and have been removed from the queue
 		Matched(Vec<T::AccountId>),
+		/// Player has left the queue without being matched.
+		Dequeued(T::AccountId),
+		/// Rating of player has changed from the old to the new rating after a game
+		RatingUpdated(T::AccountId, Rating, Rating),
+		/// Party has been created by its leader.
+		PartyCreated(T::AccountId),
+		/// Account has been invited to the party of the leader.
+		PartyInvited(T::AccountId, T::AccountId),
+		/// Account has joined the party of the leader.
+		PartyJoined(T::AccountId, T::AccountId),
+		/// Account has left the party of the leader.
+		PartyLeft(T::AccountId, T::AccountId),
+		/// Party has been disbanded by its leader.
+		PartyDisbanded(T::AccountId),
+		/// Invitation of account to the party of the leader has been withdrawn.
+		PartyUninvited(T::AccountId, T::AccountId),
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		AlreadyInParty,
+		NotInParty,
+		NotPartyLeader,
+		NotInvited,
+		PartyFull,
+		AlreadyQueued,
+		TooManyInvitations,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::create_party())]
+		pub fn create_party(origin: OriginFor<T>) -> DispatchResult {
+			let leader = ensure_signed(origin)?;
+			ensure!(!PartyOf::<T>::contains_key(&leader), Error::<T>::AlreadyInParty);
+			ensure!(!Matchmaking::<T>::is_queued(&leader), Error::<T>::AlreadyQueued);
+
+			let members = BoundedVec::try_from(sp_std::vec![leader.clone()])
+				.map_err(|_| Error::<T>::PartyFull)?;
+			Parties::<T>::insert(&leader, members);
+			PartyOf::<T>::insert(&leader, &leader);
+			Self::deposit_event(Event::PartyCreated(leader));
+			Ok(())
+		}
+
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::invite())]
+		pub fn invite(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			let leader = ensure_signed(origin)?;
+			ensure!(Parties::<T>::contains_key(&leader), Error::<T>::NotPartyLeader);
+			ensure!(!PartyOf::<T>::contains_key(&account), Error::<T>::AlreadyInParty);
+
+			Invitations::<T>::try_mutate(&leader, |invited| {
+				if !invited.contains(&account) {
+					invited
+						.try_push(account.clone())
+						.map_err(|_| Error::<T>::TooManyInvitations)?;
+				}
+				Ok::<_, Error<T>>(())
+			})?;
+			Self::deposit_event(Event::PartyInvited(leader, account));
+			Ok(())
+		}
+
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::accept_invitation())]
+		pub fn accept_invitation(origin: OriginFor<T>, leader: T::AccountId) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			ensure!(Invitations::<T>::get(&leader).contains(&account), Error::<T>::NotInvited);
+			ensure!(!PartyOf::<T>::contains_key(&account), Error::<T>::AlreadyInParty);
+			ensure!(
+				!Matchmaking::<T>::is_queued(&leader) && !Matchmaking::<T>::is_queued(&account),
+				Error::<T>::AlreadyQueued
+			);
+
+			Parties::<T>::try_mutate(&leader, |maybe_members| {
+				let members = maybe_members.as_mut().ok_or(Error::<T>::NotInvited)?;
+				members.try_push(account.clone()).map_err(|_| Error::<T>::PartyFull)
+			})?;
+			Invitations::<T>::mutate(&leader, |invited| invited.retain(|other| *other != account));
+			PartyOf::<T>::insert(&account, &leader);
+			Self::deposit_event(Event::PartyJoined(leader, account));
+			Ok(())
+		}
+
+		/// Leave the party, once it is no longer queued. The party is disbanded when its leader
+		/// leaves it.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::leave_party(T::MaxPartySize::get()))]
+		pub fn leave_party(origin: OriginFor<T>) -> DispatchResult {
+			let account = ensure_signed(origin)?;
+			let leader = PartyOf::<T>::get(&account).ok_or(Error::<T>::NotInParty)?;
+			ensure!(!Matchmaking::<T>::is_queued(&leader), Error::<T>::AlreadyQueued);
+
+			if account == leader {
+				for member in Parties::<T>::take(&leader).unwrap_or_default() {
+					PartyOf::<T>::remove(member);
+				}
+				Invitations::<T>::remove(&leader);
+				Self::deposit_event(Event::PartyDisbanded(leader));
+			} else {
+				Parties::<T>::mutate(&leader, |maybe_members| {
+					if let Some(members) = maybe_members {
+						members.retain(|member| *member != account);
+					}
+				});
+				PartyOf::<T>::remove(&account);
+				Self::deposit_event(Event::PartyLeft(leader, account));
+			}
+			Ok(())
+		}
+
+		/// Withdraw the invitation of account to the party of the caller, before it is accepted.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::uninvite())]
+		pub fn uninvite(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			let leader = ensure_signed(origin)?;
+			ensure!(Parties::<T>::contains_key(&leader), Error::<T>::NotPartyLeader);
+
+			Invitations::<T>::try_mutate(&leader, |invited| {
+				let position = invited
+					.iter()
+					.position(|other| *other == account)
+					.ok_or(Error::<T>::NotInvited)?;
+				invited.remove(position);
+				Ok::<_, Error<T>>(())
+			})?;
+			Self::deposit_event(Event::PartyUninvited(leader, account));
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn update_rating(account_id: &T::AccountId, old_rating: Rating, new_rating: Rating) {
+		Ratings::<T>::insert(account_id, new_rating);
+		Self::deposit_event(Event::RatingUpdated(account_id.clone(), old_rating, new_rating));
 	}
 }
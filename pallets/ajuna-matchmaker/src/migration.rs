@@ -0,0 +1,75 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+use sp_std::marker::PhantomData;
+
+pub mod v1 {
+	use super::*;
+
+	/// Queue of a bracket stored before its tickets were linked to each other.
+	#[derive(Encode, Decode)]
+	pub struct OldBracketRange {
+		pub start: BracketCounter,
+		pub end: BracketCounter,
+	}
+
+	/// Takes every player out of the queue stored before its tickets were linked to each other.
+	/// Those players were queued in brackets no longer matched by the board pallet, which now
+	/// splits them by stake, game mode and rating, so they are left to queue again.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut removed = 0_u64;
+			Players::<T>::translate::<T::AccountId, _>(|_, _, player| {
+				removed += 1;
+				Pallet::<T>::deposit_event(Event::Dequeued(player));
+				None
+			});
+			PlayerQueue::<T>::translate::<u8, _>(|_, _| {
+				removed += 1;
+				None
+			});
+			Brackets::<T>::translate::<OldBracketRange, _>(|_, _| {
+				removed += 1;
+				None
+			});
+
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(removed + 1, removed + 1)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+			Ok(Vec::new())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 1, "storage version not updated");
+			ensure!(Players::<T>::iter_keys().next().is_none(), "tickets left in the queue");
+			ensure!(PlayerQueue::<T>::iter_keys().next().is_none(), "players left in the queue");
+			ensure!(Brackets::<T>::iter_keys().next().is_none(), "brackets left in the queue");
+			Ok(())
+		}
+	}
+}
@@ -43,13 +43,15 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = MockUncheckedExtrinsic<Test>,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		Matchmaker: pallet_ajuna_matchmaker::{Pallet, Storage, Event<T>},
+		Matchmaker: pallet_ajuna_matchmaker::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
+	pub const MaxPartySize: u32 = 3;
+	pub const MaxInvitations: u32 = 3;
 }
 
 impl frame_system::Config for Test {
@@ -81,6 +83,9 @@ impl frame_system::Config for Test {
 
 impl pallet_ajuna_matchmaker::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type MaxPartySize = MaxPartySize;
+	type MaxInvitations = MaxInvitations;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
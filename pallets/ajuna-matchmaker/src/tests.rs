@@ -14,7 +14,11 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{mock::*, types::*, Matchmaking};
+use crate::{
+	mock::*, types::*, Brackets, Error, Invitations, Lanes, Matchmaking, Parties, PartyOf,
+	PlayerQueue, Players, Ratings,
+};
+use frame_support::{assert_noop, assert_ok};
 use frame_system::{EventRecord, Phase};
 
 fn queue_players_sorted(bracket: Bracket) -> Vec<u32> {
@@ -59,6 +63,59 @@ fn enqueue_should_emit_events_correctly() {
 	})
 }
 
+#[test]
+fn dequeue_should_remove_player_from_specified_bracket() {
+	new_test_ext().execute_with(|| {
+		[PLAYER_1, PLAYER_2, PLAYER_3].into_iter().for_each(|player| {
+			assert!(Matchmaking::<Test>::enqueue(player, BRACKET_0));
+		});
+
+		assert!(Matchmaking::<Test>::dequeue(PLAYER_2, BRACKET_0));
+		assert!(!Matchmaking::<Test>::is_queued(&PLAYER_2));
+		assert_eq!(queue_players_sorted(BRACKET_0), [PLAYER_1, PLAYER_3]);
+		assert_eq!(Brackets::<Test>::get(BRACKET_0), BracketRange { end: 3, tickets: 2 });
+		assert_eq!(Lanes::<Test>::get(BRACKET_0, 1), LaneRange { first: Some(0), last: Some(2) });
+		assert_eq!(
+			Players::<Test>::get(BRACKET_0, 0),
+			Some(Ticket { holder: PLAYER_1, prev: None, next: Some(2) })
+		);
+		assert_eq!(
+			Players::<Test>::get(BRACKET_0, 2),
+			Some(Ticket { holder: PLAYER_3, prev: Some(0), next: None })
+		);
+
+		assert_eq!(Matchmaking::<Test>::try_match(BRACKET_0, 2), Some(vec![PLAYER_1, PLAYER_3]));
+		assert_eq!(Brackets::<Test>::get(BRACKET_0), BracketRange { end: 3, tickets: 0 });
+		assert_eq!(Lanes::<Test>::get(BRACKET_0, 1), LaneRange::default());
+		assert!(Matchmaking::<Test>::try_match(BRACKET_0, 1).is_none());
+	})
+}
+
+#[test]
+fn dequeue_should_fail_when_player_is_not_queued_in_bracket() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1); // events are emitted from block 1
+
+		assert!(!Matchmaking::<Test>::dequeue(PLAYER_1, BRACKET_0));
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_1, BRACKET_0));
+		assert!(!Matchmaking::<Test>::dequeue(PLAYER_1, BRACKET_1));
+		assert!(Matchmaking::<Test>::is_queued(&PLAYER_1));
+		assert_eq!(System::events().len(), 1);
+
+		assert!(Matchmaking::<Test>::dequeue(PLAYER_1, BRACKET_0));
+		assert_eq!(System::events().len(), 2);
+		assert_eq!(
+			System::events()[1..],
+			vec![EventRecord {
+				phase: Phase::Initialization,
+				event: RuntimeEvent::Matchmaker(crate::Event::Dequeued(PLAYER_1)),
+				topics: vec![],
+			}]
+		);
+		assert!(!Matchmaking::<Test>::dequeue(PLAYER_1, BRACKET_0));
+	})
+}
+
 #[test]
 fn clear_queue_should_clear_specified_bracket() {
 	new_test_ext().execute_with(|| {
@@ -243,3 +300,413 @@ fn matchmaking_should_be_fifo() {
 		}
 	});
 }
+
+#[test]
+fn matchmaking_should_stay_fifo_when_players_leave_the_queue() {
+	new_test_ext().execute_with(|| {
+		for player in 0..10 {
+			assert!(Matchmaking::<Test>::enqueue(player, BRACKET_0));
+		}
+		assert!(Matchmaking::<Test>::dequeue(3, BRACKET_0));
+		assert!(Matchmaking::<Test>::dequeue(0, BRACKET_0));
+		assert!(Matchmaking::<Test>::dequeue(9, BRACKET_0));
+
+		assert_eq!(Matchmaking::<Test>::try_match(BRACKET_0, 4), Some(vec![1, 2, 4, 5]));
+		assert!(Matchmaking::<Test>::enqueue(3, BRACKET_0));
+		assert!(Matchmaking::<Test>::dequeue(7, BRACKET_0));
+		assert_eq!(Matchmaking::<Test>::try_match(BRACKET_0, 3), Some(vec![6, 8, 3]));
+		assert!(Matchmaking::<Test>::try_match(BRACKET_0, 1).is_none());
+	});
+}
+
+#[test]
+fn dequeue_should_unlink_the_ticket_without_moving_the_others() {
+	new_test_ext().execute_with(|| {
+		for player in 0..5 {
+			assert!(Matchmaking::<Test>::enqueue(player, BRACKET_0));
+		}
+		assert!(Matchmaking::<Test>::dequeue(2, BRACKET_0));
+
+		for player in [0, 1, 3, 4] {
+			assert_eq!(PlayerQueue::<Test>::get(player), Some((BRACKET_0, player)));
+		}
+		assert_eq!(Players::<Test>::get(BRACKET_0, 1).unwrap().next, Some(3));
+		assert_eq!(Players::<Test>::get(BRACKET_0, 3).unwrap().prev, Some(1));
+
+		assert!(Matchmaking::<Test>::dequeue(0, BRACKET_0));
+		assert!(Matchmaking::<Test>::dequeue(4, BRACKET_0));
+		assert_eq!(Brackets::<Test>::get(BRACKET_0), BracketRange { end: 5, tickets: 2 });
+		assert_eq!(Lanes::<Test>::get(BRACKET_0, 1), LaneRange { first: Some(1), last: Some(3) });
+		assert_eq!(Matchmaking::<Test>::try_match(BRACKET_0, 2), Some(vec![1, 3]));
+		assert!(Brackets::<Test>::get(BRACKET_0).is_empty());
+	});
+}
+
+#[test]
+fn clear_queue_should_reset_bracket_range() {
+	new_test_ext().execute_with(|| {
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_1, BRACKET_0));
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_2, BRACKET_0));
+		Matchmaking::<Test>::clear_queue(BRACKET_0);
+		assert_eq!(Brackets::<Test>::get(BRACKET_0), BracketRange::default());
+
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_3, BRACKET_0));
+		assert_eq!(Matchmaking::<Test>::try_match(BRACKET_0, 1), Some(vec![PLAYER_3]));
+	});
+}
+
+#[test]
+fn expected_score_should_favour_the_higher_rated_player() {
+	assert_eq!(expected_score(1_500, 1_500), 500);
+	assert_eq!(expected_score(1_700, 1_500), 760);
+	assert_eq!(expected_score(1_500, 1_700), 240);
+	assert_eq!(expected_score(3_000, 1_000), 990);
+	assert_eq!(expected_score(1_000, 3_000), 10);
+}
+
+#[test]
+fn record_win_should_update_ratings_of_winner_and_losers() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_eq!(Matchmaking::<Test>::rating(&PLAYER_1), INITIAL_RATING);
+		Matchmaking::<Test>::record_win(&[PLAYER_1], &[PLAYER_2]);
+		assert_eq!(Ratings::<Test>::get(PLAYER_1), 1_516);
+		assert_eq!(Ratings::<Test>::get(PLAYER_2), 1_484);
+		System::assert_has_event(RuntimeEvent::Matchmaker(crate::Event::RatingUpdated(
+			PLAYER_1, 1_500, 1_516,
+		)));
+		System::assert_has_event(RuntimeEvent::Matchmaker(crate::Event::RatingUpdated(
+			PLAYER_2, 1_500, 1_484,
+		)));
+
+		// beating a lower rated player is worth less than beating an equal one
+		Matchmaking::<Test>::record_win(&[PLAYER_1], &[PLAYER_2]);
+		assert_eq!(Ratings::<Test>::get(PLAYER_1), 1_530);
+		assert_eq!(Ratings::<Test>::get(PLAYER_2), 1_470);
+
+		// an upset is worth more
+		Matchmaking::<Test>::record_win(&[PLAYER_2], &[PLAYER_1]);
+		assert_eq!(Ratings::<Test>::get(PLAYER_1), 1_512);
+		assert_eq!(Ratings::<Test>::get(PLAYER_2), 1_488);
+	});
+}
+
+#[test]
+fn record_win_should_update_each_rating_once_against_the_other_side() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		// a loser against several winners is rated once, against their average rating
+		Matchmaking::<Test>::record_win(&[PLAYER_1, PLAYER_2, PLAYER_3], &[PLAYER_4]);
+		for winner in [PLAYER_1, PLAYER_2, PLAYER_3] {
+			assert_eq!(Ratings::<Test>::get(winner), 1_516);
+		}
+		assert_eq!(Ratings::<Test>::get(PLAYER_4), 1_484);
+
+		Ratings::<Test>::insert(PLAYER_5, 1_700);
+		Ratings::<Test>::insert(PLAYER_6, 1_300);
+		Matchmaking::<Test>::record_win(&[PLAYER_5, PLAYER_6], &[PLAYER_1, PLAYER_4]);
+		assert_eq!(Ratings::<Test>::get(PLAYER_5), 1_707);
+		assert_eq!(Ratings::<Test>::get(PLAYER_6), 1_324);
+		assert_eq!(Ratings::<Test>::get(PLAYER_1), 1_500);
+		assert_eq!(Ratings::<Test>::get(PLAYER_4), 1_468);
+	});
+}
+
+#[test]
+fn record_ranking_should_rate_each_place_against_the_places_above_and_below() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		Matchmaking::<Test>::record_ranking(&[
+			vec![PLAYER_1],
+			vec![PLAYER_2, PLAYER_3],
+			vec![PLAYER_4],
+		]);
+		assert_eq!(Ratings::<Test>::get(PLAYER_1), 1_532);
+		assert_eq!(Ratings::<Test>::get(PLAYER_2), 1_500);
+		assert_eq!(Ratings::<Test>::get(PLAYER_3), 1_500);
+		assert_eq!(Ratings::<Test>::get(PLAYER_4), 1_468);
+
+		// places left empty are not rated against
+		Matchmaking::<Test>::record_ranking(&[vec![PLAYER_5], vec![]]);
+		assert!(!Ratings::<Test>::contains_key(PLAYER_5));
+	});
+}
+
+#[test]
+fn bracket_for_should_follow_rating_band() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Matchmaking::<Test>::bracket_for(&PLAYER_1), INITIAL_RATING / RATING_BAND);
+
+		Ratings::<Test>::insert(PLAYER_1, 1_999);
+		Ratings::<Test>::insert(PLAYER_2, 2_000);
+		assert_eq!(Matchmaking::<Test>::bracket_for(&PLAYER_1), 9);
+		assert_eq!(Matchmaking::<Test>::bracket_for(&PLAYER_2), 10);
+	});
+}
+
+#[test]
+fn record_draw_should_move_ratings_towards_each_other() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+
+		Matchmaking::<Test>::record_draw(&[PLAYER_1, PLAYER_2]);
+		assert_eq!(Ratings::<Test>::get(PLAYER_1), INITIAL_RATING);
+		assert_eq!(Ratings::<Test>::get(PLAYER_2), INITIAL_RATING);
+
+		Ratings::<Test>::insert(PLAYER_1, 1_700);
+		Matchmaking::<Test>::record_draw(&[PLAYER_1, PLAYER_2]);
+		assert_eq!(Ratings::<Test>::get(PLAYER_1), 1_692);
+		assert_eq!(Ratings::<Test>::get(PLAYER_2), 1_508);
+		System::assert_last_event(RuntimeEvent::Matchmaker(crate::Event::RatingUpdated(
+			PLAYER_2, 1_500, 1_508,
+		)));
+	});
+}
+
+fn form_party(leader: u32, members: &[u32]) {
+	assert_ok!(Matchmaker::create_party(RuntimeOrigin::signed(leader)));
+	for member in members {
+		assert_ok!(Matchmaker::invite(RuntimeOrigin::signed(leader), *member));
+		assert_ok!(Matchmaker::accept_invitation(RuntimeOrigin::signed(*member), leader));
+	}
+}
+
+#[test]
+fn parties_are_formed_by_invitation() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Matchmaker::create_party(RuntimeOrigin::signed(PLAYER_1)));
+		assert_noop!(
+			Matchmaker::create_party(RuntimeOrigin::signed(PLAYER_1)),
+			Error::<Test>::AlreadyInParty
+		);
+		assert_noop!(
+			Matchmaker::invite(RuntimeOrigin::signed(PLAYER_2), PLAYER_3),
+			Error::<Test>::NotPartyLeader
+		);
+		assert_noop!(
+			Matchmaker::accept_invitation(RuntimeOrigin::signed(PLAYER_2), PLAYER_1),
+			Error::<Test>::NotInvited
+		);
+
+		for player in [PLAYER_2, PLAYER_3, PLAYER_4] {
+			assert_ok!(Matchmaker::invite(RuntimeOrigin::signed(PLAYER_1), player));
+		}
+		assert_ok!(Matchmaker::accept_invitation(RuntimeOrigin::signed(PLAYER_2), PLAYER_1));
+		assert_ok!(Matchmaker::accept_invitation(RuntimeOrigin::signed(PLAYER_3), PLAYER_1));
+		assert_noop!(
+			Matchmaker::accept_invitation(RuntimeOrigin::signed(PLAYER_4), PLAYER_1),
+			Error::<Test>::PartyFull
+		);
+
+		assert_eq!(Matchmaking::<Test>::party(&PLAYER_3), vec![PLAYER_1, PLAYER_2, PLAYER_3]);
+		assert!(Matchmaking::<Test>::party(&PLAYER_4).is_empty());
+		assert!(!Matchmaking::<Test>::enqueue(PLAYER_2, BRACKET_0));
+	});
+}
+
+#[test]
+fn parties_are_queued_and_matched_as_a_whole() {
+	new_test_ext().execute_with(|| {
+		form_party(PLAYER_1, &[PLAYER_2]);
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_3, BRACKET_0));
+		assert!(Matchmaking::<Test>::enqueue_party(PLAYER_1, BRACKET_0));
+		assert!(!Matchmaking::<Test>::enqueue_party(PLAYER_1, BRACKET_0));
+		assert!(!Matchmaking::<Test>::enqueue_party(PLAYER_2, BRACKET_0));
+		assert!(Matchmaking::<Test>::is_queued(&PLAYER_2));
+		assert_eq!(queue_players_sorted(BRACKET_0), [PLAYER_1, PLAYER_2, PLAYER_3]);
+
+		// the party does not fit next to the player queued before it, but makes a match alone
+		assert_eq!(Matchmaking::<Test>::try_match(BRACKET_0, 2), Some(vec![PLAYER_1, PLAYER_2]));
+		assert!(!Matchmaking::<Test>::is_queued(&PLAYER_2));
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_4, BRACKET_0));
+		assert_eq!(Matchmaking::<Test>::try_match(BRACKET_0, 2), Some(vec![PLAYER_3, PLAYER_4]));
+		assert!(Brackets::<Test>::get(BRACKET_0).is_empty());
+
+		// parties skipped for a match keep their place in the queue
+		assert!(Matchmaking::<Test>::enqueue_party(PLAYER_1, BRACKET_0));
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_3, BRACKET_0));
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_4, BRACKET_0));
+		assert_eq!(Matchmaking::<Test>::try_match(BRACKET_0, 1), Some(vec![PLAYER_3]));
+		assert_eq!(
+			Matchmaking::<Test>::try_match(BRACKET_0, 3),
+			Some(vec![PLAYER_1, PLAYER_2, PLAYER_4])
+		);
+	});
+}
+
+#[test]
+fn try_match_should_find_tickets_adding_up_to_the_match_beyond_the_oldest() {
+	new_test_ext().execute_with(|| {
+		form_party(PLAYER_2, &[PLAYER_3]);
+		form_party(PLAYER_4, &[PLAYER_5]);
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_1, BRACKET_0));
+		assert!(Matchmaking::<Test>::enqueue_party(PLAYER_2, BRACKET_0));
+		assert!(Matchmaking::<Test>::enqueue_party(PLAYER_4, BRACKET_0));
+
+		// taking the oldest ticket first leaves no tickets completing the match
+		assert_eq!(
+			Matchmaking::<Test>::try_match(BRACKET_0, 4),
+			Some(vec![PLAYER_2, PLAYER_3, PLAYER_4, PLAYER_5])
+		);
+		assert!(Matchmaking::<Test>::is_queued(&PLAYER_1));
+		assert!(Matchmaking::<Test>::try_match(BRACKET_0, 4).is_none());
+		assert!(Matchmaking::<Test>::is_queued(&PLAYER_1));
+	});
+}
+
+#[test]
+fn parties_too_large_for_the_match_do_not_block_the_players_queued_behind_them() {
+	new_test_ext().execute_with(|| {
+		for party in 0..16 {
+			let leader = 100 + 3 * party;
+			form_party(leader, &[leader + 1, leader + 2]);
+			assert!(Matchmaking::<Test>::enqueue_party(leader, BRACKET_0));
+		}
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_1, BRACKET_0));
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_2, BRACKET_0));
+
+		assert_eq!(Matchmaking::<Test>::try_match(BRACKET_0, 2), Some(vec![PLAYER_1, PLAYER_2]));
+		assert_eq!(Brackets::<Test>::get(BRACKET_0).tickets, 16);
+
+		// the oldest party still comes first once it fits in the match
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_3, BRACKET_0));
+		assert_eq!(
+			Matchmaking::<Test>::try_match(BRACKET_0, 4),
+			Some(vec![100, 101, 102, PLAYER_3])
+		);
+		assert_eq!(Lanes::<Test>::get(BRACKET_0, 3).first, Some(1));
+	});
+}
+
+#[test]
+fn invitations_are_capped_per_party_leader() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Matchmaker::create_party(RuntimeOrigin::signed(PLAYER_1)));
+		for player in [PLAYER_2, PLAYER_3, PLAYER_4] {
+			assert_ok!(Matchmaker::invite(RuntimeOrigin::signed(PLAYER_1), player));
+		}
+		assert_noop!(
+			Matchmaker::invite(RuntimeOrigin::signed(PLAYER_1), PLAYER_5),
+			Error::<Test>::TooManyInvitations
+		);
+		// inviting again an account already invited takes no more room
+		assert_ok!(Matchmaker::invite(RuntimeOrigin::signed(PLAYER_1), PLAYER_2));
+		assert_eq!(Invitations::<Test>::get(PLAYER_1).len(), 3);
+
+		// accepted invitations leave room for others
+		assert_ok!(Matchmaker::accept_invitation(RuntimeOrigin::signed(PLAYER_2), PLAYER_1));
+		assert_ok!(Matchmaker::invite(RuntimeOrigin::signed(PLAYER_1), PLAYER_5));
+		assert_eq!(Invitations::<Test>::get(PLAYER_1).to_vec(), vec![PLAYER_3, PLAYER_4, PLAYER_5]);
+
+		// disbanding the party withdraws its invitations
+		assert_ok!(Matchmaker::leave_party(RuntimeOrigin::signed(PLAYER_1)));
+		assert!(!Invitations::<Test>::contains_key(PLAYER_1));
+		assert_noop!(
+			Matchmaker::accept_invitation(RuntimeOrigin::signed(PLAYER_3), PLAYER_1),
+			Error::<Test>::NotInvited
+		);
+	});
+}
+
+#[test]
+fn invitations_can_be_withdrawn_by_the_party_leader() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Matchmaker::create_party(RuntimeOrigin::signed(PLAYER_1)));
+		for player in [PLAYER_2, PLAYER_3, PLAYER_4] {
+			assert_ok!(Matchmaker::invite(RuntimeOrigin::signed(PLAYER_1), player));
+		}
+		assert_noop!(
+			Matchmaker::uninvite(RuntimeOrigin::signed(PLAYER_2), PLAYER_3),
+			Error::<Test>::NotPartyLeader
+		);
+		assert_noop!(
+			Matchmaker::uninvite(RuntimeOrigin::signed(PLAYER_1), PLAYER_5),
+			Error::<Test>::NotInvited
+		);
+
+		assert_ok!(Matchmaker::uninvite(RuntimeOrigin::signed(PLAYER_1), PLAYER_3));
+		System::assert_last_event(RuntimeEvent::Matchmaker(crate::Event::PartyUninvited(
+			PLAYER_1, PLAYER_3,
+		)));
+		assert_eq!(Invitations::<Test>::get(PLAYER_1).to_vec(), vec![PLAYER_2, PLAYER_4]);
+		assert_noop!(
+			Matchmaker::accept_invitation(RuntimeOrigin::signed(PLAYER_3), PLAYER_1),
+			Error::<Test>::NotInvited
+		);
+
+		// the room left by the withdrawn invitation is taken by another one
+		assert_ok!(Matchmaker::invite(RuntimeOrigin::signed(PLAYER_1), PLAYER_5));
+		assert_ok!(Matchmaker::accept_invitation(RuntimeOrigin::signed(PLAYER_5), PLAYER_1));
+	});
+}
+
+#[test]
+fn a_queued_party_cannot_be_left_until_it_is_dequeued() {
+	new_test_ext().execute_with(|| {
+		form_party(PLAYER_1, &[PLAYER_2, PLAYER_3]);
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_4, BRACKET_0));
+		assert!(Matchmaking::<Test>::enqueue_party(PLAYER_1, BRACKET_0));
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_5, BRACKET_0));
+
+		for member in [PLAYER_1, PLAYER_3] {
+			assert_noop!(
+				Matchmaker::leave_party(RuntimeOrigin::signed(member)),
+				Error::<Test>::AlreadyQueued
+			);
+		}
+		assert_eq!(
+			queue_players_sorted(BRACKET_0),
+			[PLAYER_1, PLAYER_2, PLAYER_3, PLAYER_4, PLAYER_5]
+		);
+
+		assert!(Matchmaking::<Test>::dequeue(PLAYER_1, BRACKET_0));
+		assert_ok!(Matchmaker::leave_party(RuntimeOrigin::signed(PLAYER_3)));
+		assert_eq!(Matchmaking::<Test>::party(&PLAYER_2), vec![PLAYER_1, PLAYER_2]);
+		assert!(PartyOf::<Test>::get(PLAYER_3).is_none());
+
+		assert!(Matchmaking::<Test>::enqueue_party(PLAYER_1, BRACKET_0));
+		assert!(Matchmaking::<Test>::dequeue(PLAYER_1, BRACKET_0));
+		assert_ok!(Matchmaker::leave_party(RuntimeOrigin::signed(PLAYER_1)));
+		assert!(Parties::<Test>::get(PLAYER_1).is_none());
+		assert!(PartyOf::<Test>::get(PLAYER_2).is_none());
+		assert_eq!(Matchmaking::<Test>::try_match(BRACKET_0, 2), Some(vec![PLAYER_4, PLAYER_5]));
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_2, BRACKET_0));
+	});
+}
+
+#[test]
+fn migration_to_v1_takes_every_player_out_of_the_old_queue() {
+	use crate::migration::v1::{MigrateToV1, OldBracketRange};
+	use frame_support::{
+		storage::unhashed,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	};
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for (index, player) in [PLAYER_1, PLAYER_2].into_iter().enumerate() {
+			unhashed::put(&Players::<Test>::hashed_key_for(BRACKET_0, index as u32), &player);
+			unhashed::put(&PlayerQueue::<Test>::hashed_key_for(player), &1_u8);
+		}
+		unhashed::put(
+			&Brackets::<Test>::hashed_key_for(BRACKET_0),
+			&OldBracketRange { start: 0, end: 2 },
+		);
+		StorageVersion::new(0).put::<Matchmaker>();
+
+		MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(Matchmaker::on_chain_storage_version(), 1);
+		assert_eq!(Players::<Test>::iter_keys().count(), 0);
+		assert_eq!(PlayerQueue::<Test>::iter_keys().count(), 0);
+		assert_eq!(Brackets::<Test>::get(BRACKET_0), BracketRange::default());
+		System::assert_has_event(RuntimeEvent::Matchmaker(crate::Event::Dequeued(PLAYER_1)));
+		System::assert_has_event(RuntimeEvent::Matchmaker(crate::Event::Dequeued(PLAYER_2)));
+
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_1, BRACKET_0));
+		assert!(Matchmaking::<Test>::enqueue(PLAYER_2, BRACKET_0));
+		assert_eq!(Matchmaking::<Test>::try_match(BRACKET_0, 2), Some(vec![PLAYER_1, PLAYER_2]));
+	});
+}
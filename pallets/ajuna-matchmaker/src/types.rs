@@ -20,17 +20,144 @@ use sp_std::vec::Vec;
 pub const DEFAULT_BRACKET: Bracket = 0;
 pub const DEFAULT_PLAYERS: u8 = 2;
 
+/// Rating given to players who have not played any game yet
+pub const INITIAL_RATING: Rating = 1_500;
+/// Maximum number of rating points won or lost in a single game
+pub const RATING_K_FACTOR: Rating = 32;
+/// Width of the rating band covered by a single bracket
+pub const RATING_BAND: Rating = 200;
+
+/// Expected score in per mille of the higher rated player, for rating differences in steps of 25
+/// points up to 800, following the Elo rating system
+const EXPECTED_SCORES: [u32; 33] = [
+	500, 536, 571, 606, 640, 673, 703, 733, 760, 785, 808, 830, 849, 867, 882, 896, 909, 920, 930,
+	939, 947, 954, 960, 965, 969, 973, 977, 980, 983, 985, 987, 989, 990,
+];
+
 /// Type to identify a bracket
 pub type Bracket = u32;
 
 /// Type to counter items in a bracket
 pub type BracketCounter = u32;
 
-/// A range describing the start and end points of the bracket, a rudimentary FIFO
+/// Type of a player's skill rating
+pub type Rating = u32;
+
+/// The queue of a bracket. Each ticket queues either a single player or a whole party, which is
+/// never split, in the lane of the tickets of its size.
 #[derive(Default, Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
 pub struct BracketRange {
-	pub start: BracketCounter,
+	/// Index of the next ticket queued, in any lane
 	pub end: BracketCounter,
+	/// Number of tickets queued
+	pub tickets: BracketCounter,
+}
+
+impl BracketRange {
+	/// Number of tickets queued in the bracket
+	pub fn len(&self) -> BracketCounter {
+		self.tickets
+	}
+
+	/// Whether the bracket has no tickets queued
+	pub fn is_empty(&self) -> bool {
+		self.tickets == 0
+	}
+}
+
+/// The ends of a lane of a bracket, a FIFO of the tickets of the same size linked to each other
+/// so that any of them can leave it at a constant cost
+#[derive(Default, Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct LaneRange {
+	/// Index of the ticket queued first, if any
+	pub first: Option<BracketCounter>,
+	/// Index of the ticket queued last, if any
+	pub last: Option<BracketCounter>,
+}
+
+/// A ticket queued in a bracket, linked to the tickets of its lane queued right before and after
+/// it
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug, TypeInfo, MaxEncodedLen)]
+pub struct Ticket<AccountId> {
+	/// The player queued, or the leader of the party queued
+	pub holder: AccountId,
+	/// Index of the ticket of the lane queued right before, if any
+	pub prev: Option<BracketCounter>,
+	/// Index of the ticket of the lane queued right after, if any
+	pub next: Option<BracketCounter>,
+}
+
+/// Bracket covering the rating band of the given rating
+pub fn bracket_for_rating(rating: Rating) -> Bracket {
+	rating / RATING_BAND
+}
+
+/// Expected score in per mille of a player against an opponent
+pub fn expected_score(rating: Rating, opponent: Rating) -> u32 {
+	let step = rating.abs_diff(opponent).min(800) / 25;
+	let expected = EXPECTED_SCORES[step as usize];
+	if rating >= opponent {
+		expected
+	} else {
+		1_000 - expected
+	}
+}
+
+/// Rating points won by a player against an opponent, given the actual score in per mille
+pub fn rating_delta(rating: Rating, opponent: Rating, score: u32) -> i32 {
+	let expected = expected_score(rating, opponent) as i32;
+	RATING_K_FACTOR as i32 * (score as i32 - expected) / 1_000
+}
+
+/// Average of the ratings, or the initial rating when there are none
+fn average_rating(ratings: &[Rating]) -> Rating {
+	match ratings.len() as u64 {
+		0 => INITIAL_RATING,
+		count => (ratings.iter().map(|rating| *rating as u64).sum::<u64>() / count) as Rating,
+	}
+}
+
+fn apply_delta(rating: Rating, delta: i32) -> Rating {
+	if delta >= 0 {
+		rating.saturating_add(delta.unsigned_abs())
+	} else {
+		rating.saturating_sub(delta.unsigned_abs())
+	}
+}
+
+/// Picks the tickets, given with their size in FIFO order, that add up to exactly the number of
+/// players required. A ticket is picked whenever the tickets after it can complete the match, so
+/// the oldest tickets are preferred.
+fn pick_tickets(
+	tickets: &[(BracketCounter, u32)],
+	number_required: u32,
+) -> Option<Vec<BracketCounter>> {
+	let required = number_required as usize;
+	// completable[i][size] tells if the tickets from the i-th on can add up to size
+	let mut completable = Vec::from([(0..=required).map(|size| size == 0).collect::<Vec<_>>()]);
+	for (_, ticket_size) in tickets.iter().rev() {
+		let ticket_size = *ticket_size as usize;
+		let after = completable.last().expect("starts with the empty tickets; qed");
+		let row = (0..=required)
+			.map(|size| after[size] || (size >= ticket_size && after[size - ticket_size]))
+			.collect::<Vec<_>>();
+		completable.push(row);
+	}
+	completable.reverse();
+	if !completable[0][required] {
+		return None
+	}
+
+	let mut picked = Vec::new();
+	let mut left = required;
+	for (i, (index, ticket_size)) in tickets.iter().enumerate() {
+		let ticket_size = *ticket_size as usize;
+		if ticket_size <= left && completable[i + 1][left - ticket_size] {
+			picked.push(*index);
+			left -= ticket_size;
+		}
+	}
+	Some(picked)
 }
 
 /// A matchmaker trait which groups players as accounts in brackets
@@ -38,9 +165,16 @@ pub trait Matchmaker {
 	/// The identifier for player
 	type Player;
 
-	/// Enqueue account in bracket
+	/// Enqueue account in bracket, unless it is in a party
 	fn enqueue(account_id: Self::Player, bracket: Bracket) -> bool;
 
+	/// Enqueue the party led by account in bracket, as a single ticket matched as a whole
+	fn enqueue_party(leader: Self::Player, bracket: Bracket) -> bool;
+
+	/// Remove account from the queue of bracket, together with its party, keeping the order of
+	/// the other players
+	fn dequeue(account_id: Self::Player, bracket: Bracket) -> bool;
+
 	/// Clear queue for bracket
 	fn clear_queue(bracket: Bracket);
 
@@ -50,10 +184,33 @@ pub trait Matchmaker {
 	/// Those that are queued in bracket
 	fn queued_players(bracket: Bracket) -> Vec<Self::Player>;
 
-	/// Try to get a match using bracket specifying groups size.  A number of players up to
-	/// `number_required` will be returned based on availability in the bracket. The players would
-	/// be removed from the bracket and queue
+	/// Try to get a match using bracket specifying groups size. Exactly `number_required` players
+	/// will be returned based on availability in the bracket, with the members of each party next
+	/// to each other. Any tickets adding up to the match can make it, the oldest ones preferred,
+	/// among the oldest tickets of each size that fit in it. The players would be removed from
+	/// the bracket and queue
 	fn try_match(bracket: Bracket, number_required: u32) -> Option<Vec<Self::Player>>;
+
+	/// Members of the party of account, starting with its leader, or none if it is not in a party
+	fn party(account_id: &Self::Player) -> Vec<Self::Player>;
+
+	/// Current rating of account
+	fn rating(account_id: &Self::Player) -> Rating;
+
+	/// Bracket matching the rating band of account
+	fn bracket_for(account_id: &Self::Player) -> Bracket;
+
+	/// Update the ratings of the winners and the losers of a game, each player once against the
+	/// average rating of the other side
+	fn record_win(winners: &[Self::Player], losers: &[Self::Player]);
+
+	/// Update the ratings of the players of a game ranked from first to last place, with the
+	/// players tied for a place together. Each player is rated once against the average rating
+	/// of every other place, as a win over the places below and a loss to those above.
+	fn record_ranking(ranking: &[Vec<Self::Player>]);
+
+	/// Update the ratings of the players of a game that ended in a draw
+	fn record_draw(players: &[Self::Player]);
 }
 
 pub struct Matchmaking<T>(PhantomData<T>);
@@ -61,29 +218,48 @@ impl<T: Config> Matchmaker for Matchmaking<T> {
 	type Player = T::AccountId;
 
 	fn enqueue(account_id: Self::Player, bracket: Bracket) -> bool {
-		if Self::is_queued(&account_id) {
+		if Self::is_queued(&account_id) || PartyOf::<T>::contains_key(&account_id) {
 			return false
 		}
 
-		Brackets::<T>::mutate(bracket, |range| {
-			Players::<T>::insert(bracket, range.end, account_id.clone());
-			range.end += 1;
-			PlayerQueue::<T>::insert(account_id.clone(), 1);
+		Self::insert_ticket(account_id.clone(), &[account_id], bracket);
+		true
+	}
+
+	fn enqueue_party(leader: Self::Player, bracket: Bracket) -> bool {
+		let members = match Parties::<T>::get(&leader) {
+			Some(members) if !members.iter().any(Self::is_queued) => members,
+			_ => return false,
+		};
 
-			Pallet::<T>::deposit_event(Event::Queued(account_id));
-			true
-		})
+		Self::insert_ticket(leader, &members, bracket);
+		true
+	}
+
+	fn dequeue(account_id: Self::Player, bracket: Bracket) -> bool {
+		let index = match PlayerQueue::<T>::get(&account_id) {
+			Some((queued_bracket, index)) if queued_bracket == bracket => index,
+			_ => return false,
+		};
+
+		for player in Self::remove_ticket(bracket, index) {
+			Pallet::<T>::deposit_event(Event::Dequeued(player));
+		}
+		true
 	}
 
 	fn clear_queue(bracket: Bracket) {
 		let limit = Players::<T>::iter_prefix_values(bracket)
-			.map(|account_id| {
-				PlayerQueue::<T>::remove(account_id);
+			.map(|ticket| {
+				Self::ticket(&ticket.holder).into_iter().for_each(PlayerQueue::<T>::remove);
 			})
 			.count() as u32;
 		let r = Players::<T>::clear_prefix(bracket, limit, None);
 		if r.maybe_cursor.is_some() {
 			Self::clear_queue(bracket)
+		} else {
+			let _ = Lanes::<T>::clear_prefix(bracket, T::MaxPartySize::get(), None);
+			Brackets::<T>::remove(bracket);
 		}
 	}
 
@@ -92,31 +268,158 @@ impl<T: Config> Matchmaker for Matchmaking<T> {
 	}
 
 	fn queued_players(bracket: Bracket) -> Vec<Self::Player> {
-		Players::<T>::iter_prefix_values(bracket).collect()
+		Players::<T>::iter_prefix_values(bracket)
+			.flat_map(|ticket| Self::ticket(&ticket.holder))
+			.collect()
 	}
 
 	fn try_match(bracket: Bracket, number_required: u32) -> Option<Vec<Self::Player>> {
-		if Players::<T>::iter_prefix_values(bracket).count() < number_required as usize {
-			return None
+		// A match takes no more tickets of a size than fit in it, so only as many of the oldest
+		// ones of each lane are searched, however many tickets are queued behind them
+		let mut tickets = Vec::new();
+		for size in 1..=number_required.min(T::MaxPartySize::get()) {
+			let mut next = Lanes::<T>::get(bracket, size).first;
+			for _ in 0..number_required / size {
+				match next {
+					Some(index) => {
+						tickets.push((index, size));
+						next = Players::<T>::get(bracket, index).and_then(|ticket| ticket.next);
+					},
+					None => break,
+				}
+			}
 		}
+		tickets.sort_unstable();
+		let matched = pick_tickets(&tickets, number_required)?;
 
-		let players = (0..number_required)
-			.filter_map(|index| {
-				let index = index + Brackets::<T>::get(bracket).start;
-				Players::<T>::take(bracket, index)
-			})
-			.map(|player| {
-				PlayerQueue::<T>::remove(player.clone());
-				player
-			})
+		let players = matched
+			.into_iter()
+			.flat_map(|index| Self::remove_ticket(bracket, index))
+			.collect::<Vec<_>>();
+
+		Pallet::<T>::deposit_event(Event::Matched(players.clone()));
+
+		Some(players)
+	}
+
+	fn party(account_id: &Self::Player) -> Vec<Self::Player> {
+		PartyOf::<T>::get(account_id)
+			.and_then(Parties::<T>::get)
+			.map(Vec::from)
+			.unwrap_or_default()
+	}
+
+	fn rating(account_id: &Self::Player) -> Rating {
+		Ratings::<T>::get(account_id)
+	}
+
+	fn bracket_for(account_id: &Self::Player) -> Bracket {
+		bracket_for_rating(Self::rating(account_id))
+	}
+
+	fn record_win(winners: &[Self::Player], losers: &[Self::Player]) {
+		Self::record_ranking(&[winners.to_vec(), losers.to_vec()])
+	}
+
+	fn record_ranking(ranking: &[Vec<Self::Player>]) {
+		let ranking = ranking.iter().filter(|players| !players.is_empty()).collect::<Vec<_>>();
+		if ranking.len() < 2 {
+			return
+		}
+		let ratings = ranking
+			.iter()
+			.map(|players| players.iter().map(Self::rating).collect::<Vec<_>>())
 			.collect::<Vec<_>>();
+		let averages = ratings.iter().map(|ratings| average_rating(ratings)).collect::<Vec<_>>();
+
+		for (place, (players, ratings)) in ranking.iter().zip(&ratings).enumerate() {
+			players.iter().zip(ratings).for_each(|(player, rating)| {
+				let delta = averages
+					.iter()
+					.enumerate()
+					.filter(|(other_place, _)| *other_place != place)
+					.map(|(other_place, average)| {
+						let score = if other_place > place { 1_000 } else { 0 };
+						rating_delta(*rating, *average, score)
+					})
+					.sum();
+				Pallet::<T>::update_rating(player, *rating, apply_delta(*rating, delta));
+			});
+		}
+	}
+
+	fn record_draw(players: &[Self::Player]) {
+		let ratings = players.iter().map(Self::rating).collect::<Vec<_>>();
+		players.iter().zip(&ratings).for_each(|(player, rating)| {
+			let delta = ratings.iter().map(|opponent| rating_delta(*rating, *opponent, 500)).sum();
+			Pallet::<T>::update_rating(player, *rating, apply_delta(*rating, delta));
+		});
+	}
+}
 
+impl<T: Config> Matchmaking<T> {
+	/// Players queued by the ticket of holder: the members of the party it leads, or itself
+	fn ticket(holder: &T::AccountId) -> Vec<T::AccountId> {
+		Parties::<T>::get(holder)
+			.map(Vec::from)
+			.unwrap_or_else(|| sp_std::vec![holder.clone()])
+	}
+
+	fn insert_ticket(holder: T::AccountId, players: &[T::AccountId], bracket: Bracket) {
 		Brackets::<T>::mutate(bracket, |range| {
-			range.start += players.len() as u32;
+			let index = range.end;
+			Lanes::<T>::mutate(bracket, players.len() as u32, |lane| {
+				match lane.last {
+					Some(last) => Self::link(bracket, last, |ticket| ticket.next = Some(index)),
+					None => lane.first = Some(index),
+				}
+				Players::<T>::insert(
+					bracket,
+					index,
+					Ticket { holder, prev: lane.last, next: None },
+				);
+				lane.last = Some(index);
+			});
+			for player in players {
+				PlayerQueue::<T>::insert(player, (bracket, index));
+				Pallet::<T>::deposit_event(Event::Queued(player.clone()));
+			}
+			range.end += 1;
+			range.tickets += 1;
 		});
+	}
 
-		Pallet::<T>::deposit_event(Event::Matched(players.clone()));
+	/// Removes the ticket at index from bracket, returning the players it queued. The tickets of
+	/// its lane queued right before and after it are linked to each other instead.
+	fn remove_ticket(bracket: Bracket, index: BracketCounter) -> Vec<T::AccountId> {
+		let ticket = match Players::<T>::take(bracket, index) {
+			Some(ticket) => ticket,
+			None => return Vec::new(),
+		};
+		let players = Self::ticket(&ticket.holder);
+		players.iter().for_each(PlayerQueue::<T>::remove);
 
-		Some(players)
+		// Members cannot join nor leave a queued party, so it is still in the lane of its size
+		Lanes::<T>::mutate(bracket, players.len() as u32, |lane| {
+			match ticket.prev {
+				Some(prev) => Self::link(bracket, prev, |prev| prev.next = ticket.next),
+				None => lane.first = ticket.next,
+			}
+			match ticket.next {
+				Some(next) => Self::link(bracket, next, |next| next.prev = ticket.prev),
+				None => lane.last = ticket.prev,
+			}
+		});
+		Brackets::<T>::mutate(bracket, |range| range.tickets = range.tickets.saturating_sub(1));
+		players
+	}
+
+	/// Updates the links of the ticket at index in bracket to its neighbours
+	fn link(bracket: Bracket, index: BracketCounter, f: impl FnOnce(&mut Ticket<T::AccountId>)) {
+		Players::<T>::mutate(bracket, index, |ticket| {
+			if let Some(ticket) = ticket {
+				f(ticket)
+			}
+		});
 	}
 }
@@ -0,0 +1,149 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Estimated weights for pallet_ajuna_matchmaker. They were not measured: each one counts the
+//! storage accesses of its benchmark in `benchmarking.rs`, next to a rough execution time. The
+//! proof sizes add up the max encoded size of every key and value read, for the 32 byte accounts,
+//! parties of four and eight invitations of the runtime. Queuing, leaving and matching cost the
+//! same however many tickets a bracket holds, as a match searches no more of the oldest tickets
+//! of each size than fit in it, so their weights take no length. Replace them with the measured
+//! weights by running `scripts/benchmark.sh` on reference hardware, which overwrites this file.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_ajuna_matchmaker.
+pub trait WeightInfo {
+	fn enqueue() -> Weight;
+	fn dequeue() -> Weight;
+	fn try_match() -> Weight;
+	fn create_party() -> Weight;
+	fn invite() -> Weight;
+	fn uninvite() -> Weight;
+	fn accept_invitation() -> Weight;
+	fn leave_party(m: u32) -> Weight;
+}
+
+/// Weights for pallet_ajuna_matchmaker using the database weights of the runtime.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: PlayerQueue (r:1 w:1), PartyOf (r:1 w:0), Brackets (r:1 w:1), Lanes (r:1 w:1)
+	// Storage: Players (r:1 w:2)
+	fn enqueue() -> Weight {
+		Weight::from_parts(27_000_000, 317)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	// Storage: PlayerQueue (r:1 w:1), Players (r:3 w:3), Parties (r:1 w:0), Lanes (r:1 w:1)
+	// Storage: Brackets (r:1 w:1)
+	fn dequeue() -> Weight {
+		Weight::from_parts(32_000_000, 578)
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	// Storage: Lanes (r:4 w:2), Players (r:7 w:4), Parties (r:2 w:0), Brackets (r:2 w:2)
+	// Storage: PlayerQueue (r:0 w:2)
+	// Searching the oldest tickets of each size: Lanes (r:2 w:0), Players (r:3 w:0)
+	fn try_match() -> Weight {
+		Weight::from_parts(40_000_000, 1_906)
+			.saturating_add(T::DbWeight::get().reads(15_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	// Storage: PartyOf (r:1 w:1), PlayerQueue (r:1 w:0), Parties (r:0 w:1)
+	fn create_party() -> Weight {
+		Weight::from_parts(20_000_000, 136)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	// Storage: Parties (r:1 w:0), PartyOf (r:1 w:0), Invitations (r:1 w:1)
+	fn invite() -> Weight {
+		Weight::from_parts(18_000_000, 562)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Parties (r:1 w:0), Invitations (r:1 w:1)
+	fn uninvite() -> Weight {
+		Weight::from_parts(16_000_000, 501)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	// Storage: Invitations (r:1 w:1), PartyOf (r:1 w:1), PlayerQueue (r:2 w:0), Parties (r:1 w:1)
+	fn accept_invitation() -> Weight {
+		Weight::from_parts(28_000_000, 674)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	// Storage: PartyOf (r:1 w:m), PlayerQueue (r:1 w:0), Parties (r:1 w:1), Invitations (r:0 w:1)
+	fn leave_party(m: u32) -> Weight {
+		Weight::from_parts(24_000_000, 313)
+			.saturating_add(Weight::from_parts(3_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(m.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn enqueue() -> Weight {
+		Weight::from_parts(27_000_000, 317)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn dequeue() -> Weight {
+		Weight::from_parts(32_000_000, 578)
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	fn try_match() -> Weight {
+		Weight::from_parts(40_000_000, 1_906)
+			.saturating_add(RocksDbWeight::get().reads(15_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	fn create_party() -> Weight {
+		Weight::from_parts(20_000_000, 136)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn invite() -> Weight {
+		Weight::from_parts(18_000_000, 562)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn uninvite() -> Weight {
+		Weight::from_parts(16_000_000, 501)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn accept_invitation() -> Weight {
+		Weight::from_parts(28_000_000, 674)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	fn leave_party(m: u32) -> Weight {
+		Weight::from_parts(24_000_000, 313)
+			.saturating_add(Weight::from_parts(3_000_000, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(m.into())))
+	}
+}
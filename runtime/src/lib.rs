@@ -13,7 +13,8 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount,
+		NumberFor, One, Verify,
 	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
@@ -27,7 +28,8 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness,
+		StorageInfo,
 	},
 	weights::{
 		constants::{
@@ -35,9 +37,10 @@ pub use frame_support::{
 		},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
+use pallet_ajuna_board::dot4gravity::GameConfig;
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
@@ -58,6 +61,18 @@ pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::Account
 /// Balance of an account.
 pub type Balance = u128;
 
+/// Identifier of a board game.
+pub type BoardId = u32;
+
+/// A board game as stored by the board pallet.
+pub type BoardGame = pallet_ajuna_board::types::BoardGameOf<Runtime>;
+
+/// A turn played on a board, as kept in the move log of the board pallet.
+pub type BoardMove = pallet_ajuna_board::types::MoveOf<Runtime>;
+
+/// Result of a finished board game as stored by the board pallet.
+pub type GameResult = pallet_ajuna_board::types::GameResultOf<Runtime>;
+
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
@@ -100,10 +115,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -259,6 +274,8 @@ impl pallet_transaction_payment::Config for Runtime {
 	type FeeMultiplierUpdate = ConstFeeMultiplier<FeeMultiplier>;
 }
 
+impl pallet_insecure_randomness_collective_flip::Config for Runtime {}
+
 impl pallet_sudo::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -266,15 +283,54 @@ impl pallet_sudo::Config for Runtime {
 
 impl pallet_ajuna_matchmaker::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
+	type MaxPartySize = ConstU32<4>;
+	type MaxInvitations = ConstU32<8>;
+	type WeightInfo = pallet_ajuna_matchmaker::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+	pub BoardStakeTiers: Vec<Balance> = sp_std::vec![
+		1_000 * EXISTENTIAL_DEPOSIT,
+		10_000 * EXISTENTIAL_DEPOSIT,
+		100_000 * EXISTENTIAL_DEPOSIT,
+	];
+	pub BoardGameModes: Vec<GameConfig> = sp_std::vec![
+		GameConfig::CLASSIC,
+		GameConfig::QUICK,
+		GameConfig::GRAVITY,
+		GameConfig::HIDDEN_BOMBS,
+		GameConfig::FREE_FOR_ALL,
+		GameConfig::TEAMS,
+	];
+	pub const BoardRake: Permill = Permill::from_percent(5);
+	pub BoardTreasury: AccountId = PalletId(*b"aj/brdtr").into_account_truncating();
 }
+
 impl pallet_ajuna_board::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Matchmaker = pallet_ajuna_matchmaker::Matchmaking<Runtime>;
-	type BoardId = u32;
+	type BoardId = BoardId;
 	type PlayersTurn = pallet_ajuna_board::types::Turn;
 	type GameState = pallet_ajuna_board::dot4gravity::GameState<AccountId>;
+	type TurnError = pallet_ajuna_board::dot4gravity::GameError;
+	type GameConfig = GameConfig;
 	type Game = pallet_ajuna_board::dot4gravity::Game<AccountId>;
-	type Players = frame_support::traits::ConstU32<2>;
+	type GameModes = BoardGameModes;
+	type Players = frame_support::traits::ConstU32<4>;
+	type IdleBoardTimeout = ConstU32<{ 5 * MINUTES }>;
+	type Currency = Balances;
+	type StakeTiers = BoardStakeTiers;
+	type Rake = BoardRake;
+	type Treasury = BoardTreasury;
+	// NOTE: The collective flip is built from the hashes of the previous blocks, so anyone can
+	//       work out the seed of the next board one block ahead and time their queueing to it.
+	//       Set `CommitReveal` to seed the boards from secrets of their players instead.
+	type Randomness = RandomnessCollectiveFlip;
+	type CommitReveal = ConstBool<false>;
+	type BoardRetention = ConstU32<{ DAYS }>;
+	type MaxRecentResults = ConstU32<20>;
+	type MaxMoves = ConstU32<256>;
+	type WeightInfo = pallet_ajuna_board::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -294,6 +350,7 @@ construct_runtime!(
 		Sudo: pallet_sudo,
 		AjunaBoard: pallet_ajuna_board,
 		AjunaMatchmaker: pallet_ajuna_matchmaker,
+		RandomnessCollectiveFlip: pallet_insecure_randomness_collective_flip,
 	}
 );
 
@@ -332,7 +389,10 @@ pub type Executive = frame_executive::Executive<
 
 /// Add your migration logic here if you need it.
 /// https://docs.substrate.io/reference/how-to-guides/storage-migrations/basic-storage-migration/
-type Migrations = ();
+type Migrations = (
+	pallet_ajuna_matchmaker::migration::v1::MigrateToV1<Runtime>,
+	pallet_ajuna_board::migration::v1::MigrateToV1<Runtime>,
+);
 
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
@@ -346,6 +406,7 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_ajuna_board, AjunaBoard]
+		[pallet_ajuna_matchmaker, AjunaMatchmaker]
 	);
 }
 
@@ -517,6 +578,56 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl
+		pallet_ajuna_board::runtime_api::AjunaBoardApi<
+			Block,
+			AccountId,
+			BoardId,
+			BoardGame,
+			BoardMove,
+			GameResult,
+		> for Runtime
+	{
+		fn game_of(account: AccountId) -> Option<BoardId> {
+			AjunaBoard::game_of(&account)
+		}
+		fn board(board_id: BoardId) -> Option<BoardGame> {
+			AjunaBoard::board(board_id)
+		}
+		fn moves(board_id: BoardId) -> Vec<BoardMove> {
+			AjunaBoard::moves(board_id)
+		}
+		fn game_result(board_id: BoardId) -> Option<GameResult> {
+			AjunaBoard::game_result(board_id)
+		}
+		fn player_stats(account: AccountId) -> pallet_ajuna_board::types::PlayerStats {
+			AjunaBoard::player_stats(&account)
+		}
+		fn recent_results(account: AccountId) -> Vec<(BoardId, GameResult)> {
+			AjunaBoard::recent_results(&account)
+		}
+		fn next_player(board_id: BoardId) -> Option<AccountId> {
+			AjunaBoard::next_player(board_id)
+		}
+		fn is_queued(account: AccountId) -> bool {
+			AjunaBoard::is_queued(&account)
+		}
+	}
+
+	impl pallet_ajuna_board::runtime_api::Dot4GravityApi<Block, AccountId, BoardId> for Runtime {
+		fn legal_moves(
+			board_id: BoardId,
+			account: AccountId,
+		) -> Vec<pallet_ajuna_board::dot4gravity::LegalMove> {
+			AjunaBoard::board(board_id)
+				.filter(|board_game| board_game.phase == pallet_ajuna_board::types::GamePhase::Playing)
+				.map(|board_game| {
+					pallet_ajuna_board::dot4gravity::Game::legal_moves(&board_game.state, &account)
+				})
+				.unwrap_or_default()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
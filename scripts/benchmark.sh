@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+# Measures the weights of the Ajuna pallets and writes them over their weights.rs. Run it on the
+# reference hardware the chain is weighed for.
+set -e
+
+cd "$(dirname "$0")/.."
+
+cargo build --release --features runtime-benchmarks
+
+for pallet in ajuna-board ajuna-matchmaker; do
+	echo "*** Benchmarking pallet_${pallet//-/_}"
+	./target/release/ajuna-node benchmark pallet \
+		--chain=dev \
+		--pallet="pallet_${pallet//-/_}" \
+		--extrinsic='*' \
+		--steps=50 \
+		--repeat=20 \
+		--wasm-execution=compiled \
+		--heap-pages=4096 \
+		--template=.maintain/frame-weight-template.hbs \
+		--output="pallets/$pallet/src/weights.rs"
+done